@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{SoulKey, WorkType};
+
+pub const ATTESTATION_VERSION: u8 = 1;
+
+// Prepended to the serialized snapshot before signing so an attestor key can
+// never be tricked into signing something that parses as a transaction
+pub const ATTESTATION_DOMAIN: &[u8] = b"c-protocol:soul-key-attestation";
+
+/// Compact SoulKey snapshot that an attestor signs off chain for partner apps.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReputationAttestation {
+    pub version: u8,
+    pub program_id: Pubkey,
+    pub soul_key: Pubkey, // SoulKey PDA the snapshot was read from
+    pub owner: Pubkey,
+    pub evolution_level: u8,
+    pub trust_score: u64,
+    pub badge_mask: u16,
+    pub dominant_work_type: WorkType,
+    pub total_work_completed: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+impl ReputationAttestation {
+    pub fn snapshot(soul_key_address: Pubkey, soul_key: &SoulKey, clock: &Clock) -> Self {
+        Self {
+            version: ATTESTATION_VERSION,
+            program_id: crate::ID,
+            soul_key: soul_key_address,
+            owner: soul_key.owner,
+            evolution_level: soul_key.evolution_level,
+            trust_score: soul_key.current_trust(clock),
+            badge_mask: soul_key.badge_mask,
+            dominant_work_type: soul_key.dominant_work_type,
+            total_work_completed: soul_key.total_work_completed,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        }
+    }
+
+    /// Returns true if the snapshot still describes the given SoulKey account.
+    /// Stored trust lags until the next write, so it is compared as it read at
+    /// the attested time.
+    pub fn matches(&self, soul_key: &SoulKey) -> bool {
+        let attested_at = Clock {
+            slot: self.slot,
+            unix_timestamp: self.timestamp,
+            ..Clock::default()
+        };
+        self.owner == soul_key.owner
+            && self.evolution_level == soul_key.evolution_level
+            && self.trust_score == soul_key.current_trust(&attested_at)
+            && self.badge_mask == soul_key.badge_mask
+            && self.dominant_work_type == soul_key.dominant_work_type
+            && self.total_work_completed == soul_key.total_work_completed
+    }
+
+    /// The exact bytes an attestor signs: the domain tag followed by the borsh encoding.
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = ATTESTATION_DOMAIN.to_vec();
+        // Serializing into a Vec cannot fail
+        self.serialize(&mut message).unwrap();
+        message
+    }
+}
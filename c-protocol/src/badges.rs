@@ -0,0 +1,140 @@
+use crate::{BadgeType, SoulKey, WorkType};
+
+pub const BADGE_COUNT: usize = 10;
+
+// SoulKeys created before 2027-01-01 UTC qualify as early adopters
+pub const EARLY_ADOPTER_CUTOFF: i64 = 1_798_761_600;
+
+#[derive(Clone, Copy, Debug)]
+pub enum BadgeCriterion {
+    CreatedBefore(i64),
+    MinTotalWork(u64),
+    MinLevel(u8),
+    MinTrust(u64),
+    MinStreakDays(u32),
+    MinWorkTypeCount(WorkType, u32),
+    MinDistinctWorkTypes(u32),
+    MinWitnessVotesGiven(u64),
+    MinAlignedWitnessVotes(u64),
+    MinWitnessAccuracyBps(u64),
+    MinCommunityEndorsements(u32),
+    MinReferrals(u32),
+    MinLiquidityDeposits(u32),
+}
+
+impl BadgeCriterion {
+    pub fn is_met(&self, soul_key: &SoulKey) -> bool {
+        match *self {
+            BadgeCriterion::CreatedBefore(cutoff) => soul_key.creation_timestamp < cutoff,
+            BadgeCriterion::MinTotalWork(min) => soul_key.total_work_completed >= min,
+            BadgeCriterion::MinLevel(min) => soul_key.evolution_level >= min,
+            BadgeCriterion::MinTrust(min) => soul_key.trust_score >= min,
+            BadgeCriterion::MinStreakDays(min) => soul_key.consecutive_active_days >= min,
+            BadgeCriterion::MinWorkTypeCount(work_type, min) => {
+                soul_key.work_type_counts[work_type as usize] >= min
+            }
+            BadgeCriterion::MinDistinctWorkTypes(min) => {
+                soul_key.work_type_counts.iter().filter(|c| **c > 0).count() as u32 >= min
+            }
+            BadgeCriterion::MinWitnessVotesGiven(min) => soul_key.witness_votes_given >= min,
+            BadgeCriterion::MinAlignedWitnessVotes(min) => soul_key.witness_votes_aligned >= min,
+            BadgeCriterion::MinWitnessAccuracyBps(min) => soul_key.witness_accuracy_bps() >= min,
+            BadgeCriterion::MinCommunityEndorsements(min) => soul_key.community_endorsements >= min,
+            BadgeCriterion::MinReferrals(min) => soul_key.referrals_made >= min,
+            BadgeCriterion::MinLiquidityDeposits(min) => soul_key.liquidity_deposits >= min,
+        }
+    }
+}
+
+/// A badge is awarded once, the first time every criterion holds.
+pub struct BadgeRule {
+    pub badge: BadgeType,
+    pub criteria: &'static [BadgeCriterion],
+}
+
+impl BadgeRule {
+    pub fn is_met(&self, soul_key: &SoulKey) -> bool {
+        self.criteria.iter().all(|criterion| criterion.is_met(soul_key))
+    }
+}
+
+pub const BADGE_CATALOGUE: [BadgeRule; BADGE_COUNT] = [
+    BadgeRule {
+        badge: BadgeType::EarlyAdopter,
+        criteria: &[
+            BadgeCriterion::CreatedBefore(EARLY_ADOPTER_CUTOFF),
+            BadgeCriterion::MinTotalWork(10),
+        ],
+    },
+    BadgeRule {
+        badge: BadgeType::ConsistentWorker,
+        criteria: &[BadgeCriterion::MinStreakDays(30)],
+    },
+    BadgeRule {
+        badge: BadgeType::CodeMaster,
+        criteria: &[
+            BadgeCriterion::MinWorkTypeCount(WorkType::WriteCode, 100),
+            BadgeCriterion::MinLevel(5),
+        ],
+    },
+    BadgeRule {
+        badge: BadgeType::CommunityBuilder,
+        criteria: &[
+            BadgeCriterion::MinWitnessVotesGiven(100),
+            BadgeCriterion::MinCommunityEndorsements(10),
+        ],
+    },
+    BadgeRule {
+        badge: BadgeType::DealCloser,
+        criteria: &[BadgeCriterion::MinWorkTypeCount(WorkType::CloseDeal, 25)],
+    },
+    BadgeRule {
+        badge: BadgeType::Mentor,
+        criteria: &[BadgeCriterion::MinReferrals(20), BadgeCriterion::MinLevel(10)],
+    },
+    BadgeRule {
+        badge: BadgeType::Innovator,
+        criteria: &[
+            BadgeCriterion::MinDistinctWorkTypes(8),
+            BadgeCriterion::MinTotalWork(50),
+        ],
+    },
+    BadgeRule {
+        badge: BadgeType::TrustWorthy,
+        criteria: &[BadgeCriterion::MinTrust(5000)],
+    },
+    BadgeRule {
+        badge: BadgeType::LiquidityProvider,
+        criteria: &[BadgeCriterion::MinLiquidityDeposits(10)],
+    },
+    BadgeRule {
+        badge: BadgeType::WitnessReliable,
+        criteria: &[
+            BadgeCriterion::MinAlignedWitnessVotes(50),
+            BadgeCriterion::MinWitnessAccuracyBps(9000),
+        ],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::{Clock, Pubkey};
+
+    #[test]
+    fn liquidity_provider_badge_follows_attested_lp_deposits() {
+        let clock = Clock::default();
+        let mut soul_key = SoulKey::new(Pubkey::new_unique(), &clock);
+
+        // Collaboration pools no longer count toward it
+        for _ in 0..20 {
+            soul_key.add_pool_participation();
+        }
+        soul_key.record_liquidity_deposits(9, &clock).unwrap();
+        assert!(!soul_key.has_badge(BadgeType::LiquidityProvider));
+
+        soul_key.record_liquidity_deposits(10, &clock).unwrap();
+        assert!(soul_key.has_badge(BadgeType::LiquidityProvider));
+        assert!(soul_key.record_liquidity_deposits(9, &clock).is_err());
+    }
+}
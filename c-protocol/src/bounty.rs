@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+
+use crate::{CProtocolError, WorkType};
+
+pub const MAX_BOUNTY_REVIEWERS: usize = 5;
+pub const MAX_BOUNTY_DESCRIPTION_LEN: usize = 256;
+pub const MAX_BOUNTY_EFFORT_WEIGHT: u64 = 500; // 5x the base emission, same scale as record_work
+pub const BOUNTY_CLAIM_WINDOW: i64 = 14 * 86400; // A claim lapses if not approved within this
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BountyStatus {
+    Open,
+    Claimed,
+    Completed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BountyTerms {
+    pub reward_amount: u64,
+    pub work_type: WorkType,
+    pub effort_weight: u64,
+    pub description: String,
+    pub reviewers: Vec<Pubkey>, // A single reviewer or a witness quorum
+    pub approval_threshold: u8,
+}
+
+impl BountyTerms {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.reward_amount > 0, CProtocolError::InvalidBountyReward);
+        require!(
+            self.effort_weight > 0 && self.effort_weight <= MAX_BOUNTY_EFFORT_WEIGHT,
+            CProtocolError::InvalidEffortWeight
+        );
+        require!(
+            self.description.len() <= MAX_BOUNTY_DESCRIPTION_LEN,
+            CProtocolError::BountyDescriptionTooLong
+        );
+        require!(
+            !self.reviewers.is_empty() && self.reviewers.len() <= MAX_BOUNTY_REVIEWERS,
+            CProtocolError::InvalidBountyReviewers
+        );
+        for (index, reviewer) in self.reviewers.iter().enumerate() {
+            require!(
+                !self.reviewers[..index].contains(reviewer),
+                CProtocolError::InvalidBountyReviewers
+            );
+        }
+        require!(
+            self.approval_threshold > 0 && self.approval_threshold as usize <= self.reviewers.len(),
+            CProtocolError::InvalidApprovalThreshold
+        );
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Bounty {
+    pub sponsor: Pubkey,
+    pub bounty_id: u64,
+    pub reward_mint: Pubkey,
+    pub reward_amount: u64,
+    pub work_type: WorkType,
+    pub effort_weight: u64,
+    pub description: String,
+    pub reviewers: Vec<Pubkey>,
+    pub approval_threshold: u8,
+    pub approvals: Vec<Pubkey>,
+    pub claimant: Option<Pubkey>,
+    pub status: BountyStatus,
+    pub created_timestamp: i64,
+    pub claimed_timestamp: i64,
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
+
+impl Bounty {
+    pub const SPACE: usize = 8 // discriminator
+        + 32 // sponsor
+        + 8 // bounty_id
+        + 32 // reward_mint
+        + 8 // reward_amount
+        + 1 // work_type
+        + 8 // effort_weight
+        + 4 + MAX_BOUNTY_DESCRIPTION_LEN // description
+        + 4 + MAX_BOUNTY_REVIEWERS * 32 // reviewers
+        + 1 // approval_threshold
+        + 4 + MAX_BOUNTY_REVIEWERS * 32 // approvals
+        + 1 + 32 // claimant
+        + 1 // status
+        + 8 // created_timestamp
+        + 8 // claimed_timestamp
+        + 1 // bump
+        + 1; // escrow_bump
+
+    pub fn claim_deadline(&self) -> i64 {
+        self.claimed_timestamp + BOUNTY_CLAIM_WINDOW
+    }
+
+    /// A claim that was not approved in time; the bounty is open again.
+    pub fn claim_lapsed(&self, clock: &Clock) -> bool {
+        self.status == BountyStatus::Claimed && clock.unix_timestamp > self.claim_deadline()
+    }
+
+    /// Takes an open bounty, or one whose previous claim lapsed, for `claimant`.
+    /// A reviewer may claim only if the others can still reach the threshold.
+    pub fn claim(&mut self, claimant: Pubkey, clock: &Clock) -> Result<()> {
+        require!(
+            self.status == BountyStatus::Open || self.claim_lapsed(clock),
+            CProtocolError::BountyNotOpen
+        );
+        let other_reviewers = self.reviewers.iter().filter(|reviewer| **reviewer != claimant).count();
+        require!(
+            other_reviewers >= self.approval_threshold as usize,
+            CProtocolError::BountyApprovalUnreachable
+        );
+        self.claimant = Some(claimant);
+        self.status = BountyStatus::Claimed;
+        self.approvals.clear();
+        self.claimed_timestamp = clock.unix_timestamp;
+        Ok(())
+    }
+
+    pub fn is_cancellable(&self, clock: &Clock) -> bool {
+        self.status == BountyStatus::Open || self.claim_lapsed(clock)
+    }
+
+    /// Records a reviewer approval and returns true once the quorum is reached.
+    /// The claimant never counts toward it, even as a reviewer.
+    pub fn record_approval(&mut self, reviewer: Pubkey, clock: &Clock) -> Result<bool> {
+        require!(
+            self.status == BountyStatus::Claimed,
+            CProtocolError::BountyNotClaimed
+        );
+        require!(!self.claim_lapsed(clock), CProtocolError::BountyClaimLapsed);
+        require!(
+            self.reviewers.contains(&reviewer),
+            CProtocolError::NotABountyReviewer
+        );
+        require!(self.claimant != Some(reviewer), CProtocolError::BountySelfApproval);
+        require!(
+            !self.approvals.contains(&reviewer),
+            CProtocolError::BountyAlreadyApproved
+        );
+
+        self.approvals.push(reviewer);
+        let approvals = self
+            .approvals
+            .iter()
+            .filter(|approver| self.claimant != Some(**approver))
+            .count();
+        Ok(approvals >= self.approval_threshold as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounty(reviewers: Vec<Pubkey>, approval_threshold: u8) -> Bounty {
+        Bounty {
+            sponsor: Pubkey::new_unique(),
+            bounty_id: 0,
+            reward_mint: Pubkey::new_unique(),
+            reward_amount: 1_000,
+            work_type: WorkType::WriteCode,
+            effort_weight: 100,
+            description: String::new(),
+            reviewers,
+            approval_threshold,
+            approvals: Vec::new(),
+            claimant: None,
+            status: BountyStatus::Open,
+            created_timestamp: 0,
+            claimed_timestamp: 0,
+            bump: 255,
+            escrow_bump: 255,
+        }
+    }
+
+    #[test]
+    fn claimant_cannot_approve_their_own_claim() {
+        let clock = Clock::default();
+        let reviewers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+        // Every reviewer is needed, so none of them can claim
+        let mut bounty_all = bounty(reviewers.clone(), 3);
+        assert!(bounty_all.claim(reviewers[0], &clock).is_err());
+
+        let mut bounty = bounty(reviewers.clone(), 2);
+        bounty.claim(reviewers[0], &clock).unwrap();
+        assert!(bounty.record_approval(reviewers[0], &clock).is_err());
+        assert!(!bounty.record_approval(reviewers[1], &clock).unwrap());
+        assert!(bounty.record_approval(reviewers[1], &clock).is_err());
+        assert!(bounty.record_approval(Pubkey::new_unique(), &clock).is_err());
+        assert!(bounty.record_approval(reviewers[2], &clock).unwrap());
+    }
+}
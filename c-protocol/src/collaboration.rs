@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+
+use crate::{calculate_emission, CProtocolError, WorkType};
+
+pub const MAX_COLLABORATORS: usize = 8;
+pub const TOTAL_SHARE_BPS: u16 = 10_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CollaboratorSplit {
+    pub worker: Pubkey,
+    pub share_bps: u16, // Basis points of the total emission
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CollaboratorShare {
+    pub worker: Pubkey,
+    pub share_bps: u16,
+    pub emission_amount: u64,
+    pub accepted: bool,
+}
+
+#[account]
+pub struct CollaborativeWork {
+    pub initiator: Pubkey,
+    pub work_type: WorkType,
+    pub effort_weight: u64,
+    pub total_emission: u64,
+    pub metadata_hash: u64,
+    pub created_timestamp: i64,
+    pub contributors: Vec<CollaboratorShare>,
+    pub bump: u8,
+}
+
+impl CollaborativeWork {
+    pub const SPACE: usize = 8 // discriminator
+        + 32 // initiator
+        + 1 // work_type
+        + 8 // effort_weight
+        + 8 // total_emission
+        + 8 // metadata_hash
+        + 8 // created_timestamp
+        + 4 + MAX_COLLABORATORS * (32 + 2 + 8 + 1) // contributors
+        + 1; // bump
+
+    pub fn new(
+        initiator: Pubkey,
+        work_type: WorkType,
+        effort_weight: u64,
+        metadata_hash: u64,
+        splits: &[CollaboratorSplit],
+        clock: &Clock,
+        bump: u8,
+    ) -> Result<Self> {
+        validate_splits(&initiator, splits)?;
+
+        let total_emission = calculate_emission(&work_type, effort_weight)?;
+
+        let mut contributors = Vec::with_capacity(splits.len());
+        let mut allocated = 0u64;
+        for (index, split) in splits.iter().enumerate() {
+            // The last contributor absorbs rounding dust so the shares always sum to the total
+            let emission_amount = if index == splits.len() - 1 {
+                total_emission
+                    .checked_sub(allocated)
+                    .ok_or(CProtocolError::MathOverflow)?
+            } else {
+                total_emission
+                    .checked_mul(split.share_bps as u64)
+                    .ok_or(CProtocolError::MathOverflow)?
+                    .checked_div(TOTAL_SHARE_BPS as u64)
+                    .ok_or(CProtocolError::MathOverflow)?
+            };
+            allocated = allocated
+                .checked_add(emission_amount)
+                .ok_or(CProtocolError::MathOverflow)?;
+
+            contributors.push(CollaboratorShare {
+                worker: split.worker,
+                share_bps: split.share_bps,
+                emission_amount,
+                accepted: false,
+            });
+        }
+
+        Ok(Self {
+            initiator,
+            work_type,
+            effort_weight,
+            total_emission,
+            metadata_hash,
+            created_timestamp: clock.unix_timestamp,
+            contributors,
+            bump,
+        })
+    }
+
+    pub fn contributor_mut(&mut self, worker: &Pubkey) -> Result<&mut CollaboratorShare> {
+        self.contributors
+            .iter_mut()
+            .find(|c| &c.worker == worker)
+            .ok_or_else(|| error!(CProtocolError::NotACollaborator))
+    }
+
+    pub fn fully_accepted(&self) -> bool {
+        self.contributors.iter().all(|c| c.accepted)
+    }
+}
+
+fn validate_splits(initiator: &Pubkey, splits: &[CollaboratorSplit]) -> Result<()> {
+    require!(
+        splits.len() >= 2 && splits.len() <= MAX_COLLABORATORS,
+        CProtocolError::InvalidCollaboratorCount
+    );
+
+    let total_bps = splits.iter().map(|s| s.share_bps as u64).sum::<u64>();
+    require!(
+        total_bps == TOTAL_SHARE_BPS as u64 && splits.iter().all(|s| s.share_bps > 0),
+        CProtocolError::InvalidCollaboratorSplit
+    );
+
+    for (index, split) in splits.iter().enumerate() {
+        require!(
+            !splits[..index].iter().any(|s| s.worker == split.worker),
+            CProtocolError::DuplicateCollaborator
+        );
+    }
+
+    require!(
+        splits.iter().any(|s| &s.worker == initiator),
+        CProtocolError::InitiatorNotCollaborator
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(worker: Pubkey, share_bps: u16) -> CollaboratorSplit {
+        CollaboratorSplit { worker, share_bps }
+    }
+
+    fn work(initiator: Pubkey, splits: &[CollaboratorSplit]) -> Result<CollaborativeWork> {
+        CollaborativeWork::new(initiator, WorkType::WriteCode, 100, 0, splits, &Clock::default(), 255)
+    }
+
+    #[test]
+    fn last_contributor_absorbs_rounding_dust() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let work = work(a, &[split(a, 3_333), split(b, 3_333), split(c, 3_334)]).unwrap();
+        assert_eq!(work.total_emission, 2_000_000);
+        let amounts: Vec<u64> = work.contributors.iter().map(|c| c.emission_amount).collect();
+        assert_eq!(amounts, vec![666_600, 666_600, 666_800]);
+        assert_eq!(amounts.iter().sum::<u64>(), work.total_emission);
+    }
+
+    #[test]
+    fn invalid_splits_are_rejected() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // Too few or too many collaborators
+        assert!(work(a, &[split(a, 10_000)]).is_err());
+        let crowd: Vec<_> = (0..=MAX_COLLABORATORS)
+            .map(|i| split(if i == 0 { a } else { Pubkey::new_unique() }, 1))
+            .collect();
+        assert!(work(a, &crowd).is_err());
+        // Shares must be positive and sum to the whole
+        assert!(work(a, &[split(a, 5_000), split(b, 4_999)]).is_err());
+        assert!(work(a, &[split(a, 10_000), split(b, 0)]).is_err());
+        // Each worker once, and the initiator among them
+        assert!(work(a, &[split(a, 5_000), split(a, 5_000)]).is_err());
+        assert!(work(Pubkey::new_unique(), &[split(a, 5_000), split(b, 5_000)]).is_err());
+
+        assert!(work(a, &[split(a, 5_000), split(b, 5_000)]).is_ok());
+    }
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+
+use crate::CProtocolError;
+
+pub const MAX_DISTRIBUTION_WITNESSES: usize = 16;
+pub const MAX_DISTRIBUTION_LEAVES: u32 = 65_536; // Keeps the claimed bitmap at 8 KiB
+pub const MAX_DISTRIBUTION_PROOF_LEN: usize = 16; // log2(MAX_DISTRIBUTION_LEAVES)
+
+/// The mass payout for one epoch: a Merkle root over (index, worker, amount)
+/// leaves that witnesses other than the proposer approve by trust-weighted
+/// consensus. Each leaf index owns one bit in the claimed bitmap, so a worker
+/// can claim only once.
+#[account]
+#[derive(InitSpace)]
+pub struct Distribution {
+    pub protocol_state: Pubkey,
+    pub epoch: u64,
+    pub merkle_root: [u8; 32],
+    pub proposer: Pubkey,
+    pub total_amount: u64, // Sum of all leaf amounts; claims can never mint more
+    pub total_claimed: u64,
+    pub leaf_count: u32,
+    pub claimed_count: u32,
+    #[max_len(MAX_DISTRIBUTION_WITNESSES)]
+    pub approvals: Vec<Pubkey>, // Witnesses whose SoulKeys backed the root
+    pub approval_weight: u64,
+    pub approved: bool,
+    pub proposal_index: u32, // Tells a re-proposal for the epoch apart from a rejected one
+    pub bump: u8,
+    // One bit per leaf; sized by leaf_count at creation
+    #[max_len(0)]
+    pub claimed_bitmap: Vec<u8>,
+}
+
+impl Distribution {
+    /// Account size for a distribution with `leaf_count` leaves.
+    pub const fn space_for(leaf_count: u32) -> usize {
+        8 + Self::INIT_SPACE + Self::bitmap_len(leaf_count)
+    }
+
+    const fn bitmap_len(leaf_count: u32) -> usize {
+        (leaf_count as usize).div_ceil(8)
+    }
+
+    pub fn new(
+        protocol_state: Pubkey,
+        epoch: u64,
+        merkle_root: [u8; 32],
+        proposer: Pubkey,
+        total_amount: u64,
+        leaf_count: u32,
+        bump: u8,
+    ) -> Result<Self> {
+        require!(
+            leaf_count > 0 && leaf_count <= MAX_DISTRIBUTION_LEAVES && total_amount > 0,
+            CProtocolError::InvalidDistribution
+        );
+        Ok(Self {
+            protocol_state,
+            epoch,
+            merkle_root,
+            proposer,
+            total_amount,
+            total_claimed: 0,
+            leaf_count,
+            claimed_count: 0,
+            approvals: Vec::new(),
+            approval_weight: 0,
+            approved: false,
+            proposal_index: 0,
+            bump,
+            claimed_bitmap: vec![0; Self::bitmap_len(leaf_count)],
+        })
+    }
+
+    /// How an approval cast on proposal `proposal_index` at this address
+    /// resolved: aligned if that proposal was approved, misaligned if it was
+    /// rejected (its account closed or replaced by a later proposal), and
+    /// None while it is still open.
+    pub fn approval_outcome(subject: &AccountInfo, proposal_index: u32) -> Result<Option<bool>> {
+        if subject.owner != &crate::ID || subject.data_is_empty() {
+            return Ok(Some(false));
+        }
+        let distribution = Distribution::try_deserialize(&mut &subject.try_borrow_data()?[..])
+            .map_err(|_| error!(CProtocolError::InvalidWitnessVoteSubject))?;
+        if distribution.proposal_index != proposal_index {
+            return Ok(Some(false));
+        }
+        Ok(distribution.approved.then_some(true))
+    }
+
+    /// Adds a witness's weight and returns true once `quorum` is reached.
+    pub fn record_approval(&mut self, witness: Pubkey, weight: u64, quorum: u64) -> Result<bool> {
+        require!(witness != self.proposer, CProtocolError::ProposerCannotApprove);
+        require!(weight > 0 && quorum > 0, CProtocolError::WitnessNotEligible);
+        require!(
+            !self.approvals.contains(&witness),
+            CProtocolError::DistributionAlreadyApproved
+        );
+        require!(
+            self.approvals.len() < MAX_DISTRIBUTION_WITNESSES,
+            CProtocolError::DistributionWitnessesFull
+        );
+
+        self.approvals.push(witness);
+        self.approval_weight = self.approval_weight.saturating_add(weight);
+        self.approved = self.approval_weight >= quorum;
+        Ok(self.approved)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed_bitmap[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Flips the leaf's bit and books the amount against the distribution total.
+    pub fn mark_claimed(&mut self, index: u32, amount: u64) -> Result<()> {
+        require!(index < self.leaf_count, CProtocolError::InvalidDistributionProof);
+        require!(!self.is_claimed(index), CProtocolError::DistributionAlreadyClaimed);
+
+        let total_claimed = self
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(CProtocolError::MathOverflow)?;
+        require!(
+            total_claimed <= self.total_amount,
+            CProtocolError::DistributionExhausted
+        );
+
+        self.claimed_bitmap[index as usize / 8] |= 1 << (index % 8);
+        self.total_claimed = total_claimed;
+        self.claimed_count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(owner: Pubkey, mut data: Vec<u8>, proposal_index: u32) -> Result<Option<bool>> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 1;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        Distribution::approval_outcome(&info, proposal_index)
+    }
+
+    fn account_data(approved: bool, proposal_index: u32) -> Vec<u8> {
+        let mut distribution =
+            Distribution::new(Pubkey::new_unique(), 1, [7; 32], Pubkey::new_unique(), 100, 4, 255)
+                .unwrap();
+        distribution.approved = approved;
+        distribution.proposal_index = proposal_index;
+        let mut data = Vec::new();
+        distribution.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn approval_outcome_follows_the_voted_proposal() {
+        assert_eq!(outcome(crate::ID, account_data(true, 0), 0).unwrap(), Some(true));
+        assert_eq!(outcome(crate::ID, account_data(false, 0), 0).unwrap(), None);
+        // Rejected: closed, or re-proposed under a later index
+        assert_eq!(outcome(System::id(), Vec::new(), 0).unwrap(), Some(false));
+        assert_eq!(outcome(crate::ID, account_data(true, 1), 0).unwrap(), Some(false));
+        assert!(outcome(crate::ID, vec![1; 64], 0).is_err());
+    }
+}
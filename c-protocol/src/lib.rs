@@ -1,5 +1,38 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, MintTo};
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Mint, MintTo};
+use anchor_spl::token_2022::{self, InitializeMint2, Token2022};
+
+pub mod attestation;
+pub mod badges;
+pub mod bounty;
+pub mod collaboration;
+pub mod distribution;
+pub mod merkle;
+pub mod migration;
+pub mod organization;
+pub mod recovery;
+pub mod rewards;
+pub mod soul_key;
+pub mod soul_nft;
+pub mod trust;
+pub mod vesting;
+
+pub use attestation::*;
+pub use badges::*;
+pub use bounty::*;
+pub use collaboration::*;
+pub use distribution::*;
+pub use merkle::*;
+pub use migration::*;
+pub use organization::*;
+pub use recovery::*;
+pub use rewards::*;
+pub use soul_key::*;
+pub use soul_nft::*;
+pub use trust::*;
+pub use vesting::*;
 
 declare_id!("CProtoco1WorkChain11111111111111111111111");
 
@@ -12,8 +45,498 @@ pub mod c_protocol {
         protocol_state.total_work_recorded = 0;
         protocol_state.total_tokens_emitted = 0;
         protocol_state.decay_rate = 100; // 1% per epoch
-        protocol_state.witness_threshold = 6000; // 60% consensus required
+        protocol_state.witness_threshold = 6000; // 60% of registered witness trust
+        protocol_state.total_witness_trust = 0;
         protocol_state.min_stake_to_emit = 1_000_000; // 0.001 SOL
+        protocol_state.authority = ctx.accounts.authority.key();
+        protocol_state.streak_break_days = 1; // Missing a full day breaks the streak
+        protocol_state.attestor = ctx.accounts.authority.key();
+        protocol_state.epoch_duration = 0; // Set when reward epochs start
+        protocol_state.decay_destination = Pubkey::default(); // Early exits are blocked until set
+        protocol_state.vesting_schedules = [VestingSchedule::default(); WORK_TYPE_COUNT];
+        Ok(())
+    }
+
+    pub fn set_streak_break_days(ctx: Context<UpdateProtocolConfig>, streak_break_days: u16) -> Result<()> {
+        require!(streak_break_days > 0, CProtocolError::InvalidStreakBreakDays);
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.streak_break_days = streak_break_days;
+
+        emit!(ProtocolConfigUpdated {
+            authority: protocol_state.authority,
+            streak_break_days,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_attestor(ctx: Context<UpdateProtocolConfig>, attestor: Pubkey) -> Result<()> {
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.attestor = attestor;
+
+        emit!(AttestorUpdated {
+            authority: protocol_state.authority,
+            attestor,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_decay_destination(ctx: Context<UpdateProtocolConfig>, decay_destination: Pubkey) -> Result<()> {
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.decay_destination = decay_destination;
+
+        emit!(DecayDestinationUpdated {
+            authority: protocol_state.authority,
+            decay_destination,
+        });
+
+        Ok(())
+    }
+
+    // Applies to mints from now on; existing escrows keep the schedule they were created with
+    pub fn set_vesting_schedule(
+        ctx: Context<UpdateProtocolConfig>,
+        work_type: WorkType,
+        schedule: VestingSchedule,
+    ) -> Result<()> {
+        schedule.validate()?;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.vesting_schedules[work_type as usize] = schedule;
+
+        emit!(VestingScheduleUpdated {
+            organization: None,
+            work_type,
+            schedule,
+        });
+
+        Ok(())
+    }
+
+    pub fn start_reward_epochs(
+        ctx: Context<StartRewardEpochs>,
+        epoch_emission: u64,
+        epoch_duration: i64,
+    ) -> Result<()> {
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        require!(protocol_state.epoch_duration == 0, CProtocolError::RewardEpochsAlreadyStarted);
+        require!(epoch_duration > 0, CProtocolError::InvalidEpochConfig);
+
+        protocol_state.c_token_mint = ctx.accounts.c_token_mint.key();
+        protocol_state.epoch_emission = epoch_emission;
+        protocol_state.epoch_duration = epoch_duration;
+        protocol_state.current_epoch = 0;
+
+        let clock = Clock::get()?;
+        let reward_epoch = &mut ctx.accounts.reward_epoch;
+        reward_epoch.set_inner(RewardEpoch::new(
+            protocol_state.key(),
+            0,
+            clock.unix_timestamp,
+            epoch_duration,
+            epoch_emission,
+            ctx.bumps.reward_epoch,
+        ));
+
+        emit!(RewardEpochOpened {
+            epoch: 0,
+            start_timestamp: reward_epoch.start_timestamp,
+            end_timestamp: reward_epoch.end_timestamp,
+            emission_budget: epoch_emission,
+        });
+
+        Ok(())
+    }
+
+    // Takes effect from the next epoch so an open epoch's budget never changes
+    pub fn set_epoch_emission(ctx: Context<UpdateProtocolConfig>, epoch_emission: u64) -> Result<()> {
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.epoch_emission = epoch_emission;
+
+        emit!(EpochEmissionUpdated {
+            authority: protocol_state.authority,
+            epoch_emission,
+        });
+
+        Ok(())
+    }
+
+    pub fn close_reward_epoch(ctx: Context<CloseRewardEpoch>) -> Result<()> {
+        let clock = Clock::get()?;
+        let reward_epoch = &mut ctx.accounts.reward_epoch;
+        require!(
+            clock.unix_timestamp >= reward_epoch.end_timestamp,
+            CProtocolError::RewardEpochStillOpen
+        );
+        reward_epoch.closed = true;
+
+        emit!(RewardEpochClosed {
+            epoch: reward_epoch.epoch,
+            total_effort_points: reward_epoch.total_effort_points,
+        });
+
+        // The next epoch starts now rather than at the old end, so a late close
+        // never opens an epoch that has already expired
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        protocol_state.current_epoch += 1;
+        let next_epoch = &mut ctx.accounts.next_epoch;
+        next_epoch.set_inner(RewardEpoch::new(
+            protocol_state.key(),
+            protocol_state.current_epoch,
+            clock.unix_timestamp,
+            protocol_state.epoch_duration,
+            protocol_state.epoch_emission,
+            ctx.bumps.next_epoch,
+        ));
+
+        emit!(RewardEpochOpened {
+            epoch: next_epoch.epoch,
+            start_timestamp: next_epoch.start_timestamp,
+            end_timestamp: next_epoch.end_timestamp,
+            emission_budget: next_epoch.emission_budget,
+        });
+
+        Ok(())
+    }
+
+    // The root commits to each worker's effort points, indexed off chain from
+    // WorkRecorded events; payouts are computed on chain from those points
+    pub fn post_epoch_rewards_root(
+        ctx: Context<PostEpochRewardsRoot>,
+        _epoch: u64,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        let reward_epoch = &mut ctx.accounts.reward_epoch;
+        require!(reward_epoch.closed, CProtocolError::RewardEpochStillOpen);
+        require!(
+            reward_epoch.merkle_root.is_none(),
+            CProtocolError::RewardRootAlreadyPosted
+        );
+        reward_epoch.merkle_root = Some(merkle_root);
+
+        emit!(EpochRewardsRootPosted {
+            epoch: reward_epoch.epoch,
+            merkle_root,
+        });
+
+        Ok(())
+    }
+
+    pub fn claim_epoch_rewards(
+        ctx: Context<ClaimEpochRewards>,
+        effort_points: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require!(proof.len() <= MAX_REWARD_PROOF_LEN, CProtocolError::InvalidRewardProof);
+        let claimant = ctx.accounts.claimant.key();
+        let reward_epoch = &mut ctx.accounts.reward_epoch;
+        let merkle_root = reward_epoch
+            .merkle_root
+            .ok_or(CProtocolError::RewardRootNotPosted)?;
+
+        let leaf = merkle_leaf(&[claimant.as_ref(), &effort_points.to_le_bytes()]);
+        require!(
+            verify_merkle_proof(&proof, &merkle_root, leaf),
+            CProtocolError::InvalidRewardProof
+        );
+
+        let amount = reward_epoch.reward_for(effort_points)?;
+        reward_epoch.record_claim(amount)?;
+
+        let schedule = ctx.accounts.protocol_state.pooled_vesting_schedule();
+        let destination = reward_destination(
+            &schedule,
+            &ctx.accounts.claimant_token_account,
+            &ctx.accounts.vesting_escrow,
+            &ctx.accounts.vesting_vault,
+        )?;
+
+        let protocol_state_key = ctx.accounts.protocol_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            PROTOCOL_AUTHORITY_SEED,
+            protocol_state_key.as_ref(),
+            &[ctx.bumps.protocol_authority],
+        ]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.c_token_mint.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.protocol_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        if let Some(vesting_escrow) = &mut ctx.accounts.vesting_escrow {
+            open_vesting_escrow(
+                vesting_escrow,
+                &mut ctx.accounts.worker,
+                schedule,
+                amount,
+                ctx.bumps.vesting_escrow,
+                ctx.bumps.vesting_vault,
+            )?;
+        }
+
+        ctx.accounts.epoch_claim.set_inner(EpochClaim {
+            reward_epoch: reward_epoch.key(),
+            claimant,
+            effort_points,
+            amount,
+        });
+
+        emit!(EpochRewardsClaimed {
+            epoch: reward_epoch.epoch,
+            claimant,
+            effort_points,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    // Qualifying SoulKeys join the witness set; their trust counts toward the
+    // total that distribution consensus is measured against
+    pub fn register_witness(ctx: Context<RegisterWitness>) -> Result<()> {
+        let clock = Clock::get()?;
+        let witness_soul_key = &mut ctx.accounts.witness_soul_key;
+        witness_soul_key.refresh_trust_epoch(&clock);
+        let weight = witness_soul_key.witness_weight(&clock);
+        require!(weight > 0, CProtocolError::WitnessNotEligible);
+
+        let registration = &mut ctx.accounts.witness_registration;
+        registration.soul_key = witness_soul_key.key();
+        registration.weight = 0;
+        registration.seeded = false;
+        registration.bump = ctx.bumps.witness_registration;
+        ctx.accounts.protocol_state.reweigh_witness(registration, weight);
+
+        emit!(WitnessWeightUpdated {
+            soul_key: registration.soul_key,
+            weight,
+            total_witness_trust: ctx.accounts.protocol_state.total_witness_trust,
+        });
+
+        Ok(())
+    }
+
+    // Bootstraps the witness set: no SoulKey can earn the resolved votes that
+    // registration needs before witnesses exist to vote with
+    pub fn seed_witness(ctx: Context<SeedWitness>) -> Result<()> {
+        let clock = Clock::get()?;
+        let witness_soul_key = &mut ctx.accounts.witness_soul_key;
+        witness_soul_key.refresh_trust_epoch(&clock);
+
+        let registration = &mut ctx.accounts.witness_registration;
+        registration.soul_key = witness_soul_key.key();
+        registration.weight = 0;
+        registration.seeded = true;
+        registration.bump = ctx.bumps.witness_registration;
+        let weight = registration.eligible_weight(witness_soul_key, &clock);
+        require!(weight > 0, CProtocolError::WitnessNotEligible);
+        ctx.accounts.protocol_state.reweigh_witness(registration, weight);
+
+        emit!(WitnessWeightUpdated {
+            soul_key: registration.soul_key,
+            weight,
+            total_witness_trust: ctx.accounts.protocol_state.total_witness_trust,
+        });
+
+        Ok(())
+    }
+
+    // Permissionless: anyone may bring a witness's weight in line with its
+    // current trust, dropping it to zero once it stops qualifying
+    pub fn refresh_witness(ctx: Context<RefreshWitness>) -> Result<()> {
+        let clock = Clock::get()?;
+        let registration = &mut ctx.accounts.witness_registration;
+        let weight = registration.eligible_weight(&ctx.accounts.witness_soul_key, &clock);
+        ctx.accounts.protocol_state.reweigh_witness(registration, weight);
+
+        emit!(WitnessWeightUpdated {
+            soul_key: registration.soul_key,
+            weight,
+            total_witness_trust: ctx.accounts.protocol_state.total_witness_trust,
+        });
+
+        Ok(())
+    }
+
+    // One distribution per epoch, capped at what is left of the epoch's emission
+    // budget; its claims draw on that same budget. The proposer does not vote;
+    // other witnesses approve it.
+    pub fn propose_distribution(
+        ctx: Context<ProposeDistribution>,
+        epoch: u64,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        leaf_count: u32,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let witness_soul_key = &mut ctx.accounts.witness_soul_key;
+        witness_soul_key.refresh_trust_epoch(&clock);
+        require!(
+            ctx.accounts.witness_registration.eligible_weight(witness_soul_key, &clock) > 0,
+            CProtocolError::WitnessNotEligible
+        );
+        require!(
+            total_amount <= ctx.accounts.reward_epoch.remaining_budget(),
+            CProtocolError::DistributionExceedsEpochBudget
+        );
+
+        let distribution = &mut ctx.accounts.distribution;
+        distribution.set_inner(Distribution::new(
+            ctx.accounts.protocol_state.key(),
+            epoch,
+            merkle_root,
+            ctx.accounts.witness.key(),
+            total_amount,
+            leaf_count,
+            ctx.bumps.distribution,
+        )?);
+        let reward_epoch = &mut ctx.accounts.reward_epoch;
+        distribution.proposal_index = reward_epoch.distribution_proposals;
+        reward_epoch.distribution_proposals += 1;
+
+        emit!(DistributionProposed {
+            distribution: distribution.key(),
+            epoch,
+            merkle_root,
+            total_amount,
+            leaf_count,
+            proposer: ctx.accounts.witness.key(),
+        });
+
+        Ok(())
+    }
+
+    // Clears an unapproved proposal so the epoch can be proposed again
+    pub fn reject_distribution(ctx: Context<RejectDistribution>) -> Result<()> {
+        let distribution = &ctx.accounts.distribution;
+        require!(!distribution.approved, CProtocolError::DistributionAlreadyFinalized);
+
+        emit!(DistributionRejected {
+            distribution: distribution.key(),
+            epoch: distribution.epoch,
+            proposer: distribution.proposer,
+        });
+
+        Ok(())
+    }
+
+    pub fn approve_distribution(ctx: Context<ApproveDistribution>) -> Result<()> {
+        let clock = Clock::get()?;
+        let witness_soul_key = &mut ctx.accounts.witness_soul_key;
+        witness_soul_key.refresh_trust_epoch(&clock);
+        // A registration that has not caught up with decayed trust counts for no
+        // more than the trust the witness holds now
+        let registration = &ctx.accounts.witness_registration;
+        let weight = registration
+            .weight
+            .min(registration.eligible_weight(witness_soul_key, &clock));
+
+        let distribution = &mut ctx.accounts.distribution;
+        require!(!distribution.approved, CProtocolError::DistributionAlreadyFinalized);
+        let approved = distribution.record_approval(
+            ctx.accounts.witness.key(),
+            weight,
+            ctx.accounts.protocol_state.witness_quorum()?,
+        )?;
+        witness_soul_key.add_witness_vote(true);
+        ctx.accounts.witness_vote.set_inner(WitnessVote {
+            subject: distribution.key(),
+            proposal_index: distribution.proposal_index,
+            soul_key: witness_soul_key.key(),
+            cast_timestamp: clock.unix_timestamp,
+            settled: false,
+            bump: ctx.bumps.witness_vote,
+        });
+
+        emit!(DistributionApproved {
+            distribution: distribution.key(),
+            witness: ctx.accounts.witness.key(),
+            weight,
+            approval_weight: distribution.approval_weight,
+            approved,
+        });
+
+        Ok(())
+    }
+
+    // Permissionless: anyone may crank a claim, but tokens only ever reach
+    // the worker's own token account, or its vesting vault while pooled
+    // rewards vest
+    pub fn claim_distribution(
+        ctx: Context<ClaimDistribution>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require!(
+            proof.len() <= MAX_DISTRIBUTION_PROOF_LEN,
+            CProtocolError::InvalidDistributionProof
+        );
+        let worker = ctx.accounts.worker.key();
+        let distribution = &mut ctx.accounts.distribution;
+        require!(distribution.approved, CProtocolError::DistributionNotApproved);
+
+        let leaf = merkle_leaf(&[&index.to_le_bytes(), worker.as_ref(), &amount.to_le_bytes()]);
+        require!(
+            verify_merkle_proof(&proof, &distribution.merkle_root, leaf),
+            CProtocolError::InvalidDistributionProof
+        );
+        distribution.mark_claimed(index, amount)?;
+        ctx.accounts.reward_epoch.record_claim(amount)?;
+
+        let schedule = ctx.accounts.protocol_state.pooled_vesting_schedule();
+        let destination = reward_destination(
+            &schedule,
+            &ctx.accounts.worker_token_account,
+            &ctx.accounts.vesting_escrow,
+            &ctx.accounts.vesting_vault,
+        )?;
+
+        let protocol_state_key = ctx.accounts.protocol_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            PROTOCOL_AUTHORITY_SEED,
+            protocol_state_key.as_ref(),
+            &[ctx.bumps.protocol_authority],
+        ]];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.c_token_mint.to_account_info(),
+                    to: destination,
+                    authority: ctx.accounts.protocol_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        if let Some(vesting_escrow) = &mut ctx.accounts.vesting_escrow {
+            open_vesting_escrow(
+                vesting_escrow,
+                &mut ctx.accounts.worker,
+                schedule,
+                amount,
+                ctx.bumps.vesting_escrow,
+                ctx.bumps.vesting_vault,
+            )?;
+        }
+
+        emit!(DistributionClaimed {
+            distribution: distribution.key(),
+            worker,
+            index,
+            amount,
+            claimed_count: distribution.claimed_count,
+        });
+
         Ok(())
     }
 
@@ -35,41 +558,34 @@ pub mod c_protocol {
         // Anti-gaming: Prevent duplicate tasks within cooldown
         let clock = Clock::get()?;
         let task_hash = hash_task(&work_type, &task_metadata, worker.key());
-        
-        require!(
-            !worker.recent_tasks.contains(&task_hash) ||
-            clock.unix_timestamp > worker.last_work_timestamp + get_cooldown(&work_type),
-            CProtocolError::TaskCooldownActive
-        );
+        track_task(worker, task_hash, get_cooldown(&work_type), &clock)?;
 
         // Calculate emission based on work type and effort weight
-        let base_emission = get_base_emission(&work_type);
-        let emission_amount = base_emission
-            .checked_mul(effort_weight)
-            .ok_or(CProtocolError::MathOverflow)?
-            .checked_div(100)
-            .ok_or(CProtocolError::MathOverflow)?;
+        let emission_amount = calculate_emission(&work_type, effort_weight)?;
 
-        // Update worker state
-        worker.total_work_completed += 1;
-        worker.last_work_timestamp = clock.unix_timestamp;
-        worker.pending_tokens += emission_amount;
-        worker.work_diversity_score = calculate_diversity_score(&worker.work_history, &work_type);
-        
-        // Add to recent tasks for anti-gaming
-        if worker.recent_tasks.len() >= 10 {
-            worker.recent_tasks.remove(0);
-        }
-        worker.recent_tasks.push(task_hash);
-        
-        // Add to work history
-        worker.work_history.push(WorkRecord {
+        // Effort points share in the current epoch's fixed emission budget
+        let effort_points = accrue_effort(
+            protocol_state,
+            ctx.accounts.reward_epoch.as_deref_mut(),
+            ctx.accounts.soul_key.as_deref(),
+            emission_amount,
+            &clock,
+        )?;
+
+        // Update worker state and work history
+        let record = WorkRecord {
             work_type,
             effort_weight,
             timestamp: clock.unix_timestamp,
             emission_amount,
             metadata_hash: hash_string(&task_metadata),
-        });
+            organization: None,
+            unminted_amount: emission_amount,
+        };
+        if let Some(soul_key) = ctx.accounts.soul_key.as_deref_mut() {
+            soul_key.update_after_work(&record, protocol_state.streak_break_days, &clock)?;
+        }
+        push_work_record(worker, record, protocol_state);
 
         // Update protocol stats
         protocol_state.total_work_recorded += 1;
@@ -80,6 +596,8 @@ pub mod c_protocol {
             work_type,
             effort_weight,
             emission_amount,
+            effort_points,
+            reward_epoch: protocol_state.current_epoch,
             timestamp: clock.unix_timestamp,
             requires_witness: emission_amount > 1_000_000_000, // High-value needs consensus
         });
@@ -87,241 +605,2632 @@ pub mod c_protocol {
         Ok(())
     }
 
-    pub fn apply_decay(ctx: Context<ApplyDecay>) -> Result<()> {
-        let worker = &mut ctx.accounts.worker;
-        let protocol_state = &ctx.accounts.protocol_state;
+    pub fn initialize_soul_key(ctx: Context<InitializeSoulKey>) -> Result<()> {
         let clock = Clock::get()?;
+        let soul_key = &mut ctx.accounts.soul_key;
+        soul_key.set_inner(SoulKey::new(ctx.accounts.owner.key(), &clock));
+        Ok(())
+    }
 
-        // Calculate time since last activity
-        let time_inactive = clock.unix_timestamp - worker.last_activity_timestamp;
-        let epochs_inactive = time_inactive / 86400; // 1 day = 1 epoch
+    // Permissionless: the vote is aligned exactly when the distribution it
+    // approved was finalized, and misaligned once that proposal was rejected
+    pub fn settle_witness_vote(ctx: Context<SettleWitnessVote>) -> Result<()> {
+        let clock = Clock::get()?;
+        let aligned = Distribution::approval_outcome(
+            &ctx.accounts.subject.to_account_info(),
+            ctx.accounts.witness_vote.proposal_index,
+        )?
+        .ok_or(CProtocolError::WitnessVoteUnresolved)?;
+        ctx.accounts.witness_vote.settle()?;
+        let soul_key = &mut ctx.accounts.witness_soul_key;
+        soul_key.settle_witness_vote(aligned, &clock);
 
-        if epochs_inactive > 0 {
-            // Apply exponential decay
-            let decay_multiplier = protocol_state.decay_rate as u64;
-            let decay_amount = worker.pending_tokens
-                .checked_mul(decay_multiplier)
-                .ok_or(CProtocolError::MathOverflow)?
-                .checked_mul(epochs_inactive as u64)
-                .ok_or(CProtocolError::MathOverflow)?
-                .checked_div(10000)
-                .ok_or(CProtocolError::MathOverflow)?;
+        emit!(WitnessVoteSettled {
+            vote: ctx.accounts.witness_vote.key(),
+            witness: soul_key.owner,
+            aligned,
+            slashed: false,
+            trust_score: soul_key.trust_score,
+            accuracy_bps: soul_key.witness_accuracy_bps(),
+        });
 
-            worker.pending_tokens = worker.pending_tokens.saturating_sub(decay_amount);
-            worker.total_tokens_decayed += decay_amount;
+        Ok(())
+    }
 
-            emit!(TokensDecayed {
-                worker: worker.key(),
-                decay_amount,
-                remaining_balance: worker.pending_tokens,
-                epochs_inactive: epochs_inactive as u64,
-            });
-        }
+    pub fn slash_witness_vote(ctx: Context<ReportWitnessOutcome>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.witness_vote.settle()?;
+        let soul_key = &mut ctx.accounts.witness_soul_key;
+        soul_key.penalize_slashed_vote(&clock);
+
+        emit!(WitnessVoteSettled {
+            vote: ctx.accounts.witness_vote.key(),
+            witness: soul_key.owner,
+            aligned: false,
+            slashed: true,
+            trust_score: soul_key.trust_score,
+            accuracy_bps: soul_key.witness_accuracy_bps(),
+        });
+
+        Ok(())
+    }
+
+    // Read-only snapshot returned via return data; the attestor simulates this
+    // instruction and signs `signing_message()` of the result off chain
+    pub fn attest_soul_key(ctx: Context<AttestSoulKey>) -> Result<ReputationAttestation> {
+        let clock = Clock::get()?;
+        let attestation = ReputationAttestation::snapshot(
+            ctx.accounts.soul_key.key(),
+            &ctx.accounts.soul_key,
+            &clock,
+        );
+
+        emit!(SoulKeyAttested {
+            soul_key: attestation.soul_key,
+            owner: attestation.owner,
+            slot: attestation.slot,
+        });
+
+        Ok(attestation)
+    }
+
+    // The attestor indexes LiquidityDeposited events from partner pools and
+    // reports each owner's running total toward the LiquidityProvider badge
+    pub fn record_liquidity_deposits(ctx: Context<RecordLiquidityDeposits>, total_deposits: u32) -> Result<()> {
+        let clock = Clock::get()?;
+        let soul_key = &mut ctx.accounts.soul_key;
+        soul_key.record_liquidity_deposits(total_deposits, &clock)?;
+
+        emit!(LiquidityDepositsRecorded {
+            soul_key: soul_key.key(),
+            owner: soul_key.owner,
+            total_deposits,
+            badge_mask: soul_key.badge_mask,
+        });
+
+        Ok(())
+    }
+
+    pub fn initialize_recovery(
+        ctx: Context<InitializeRecovery>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        SoulKeyRecovery::validate_guardians(&ctx.accounts.owner.key(), &guardians, threshold)?;
+
+        let recovery = &mut ctx.accounts.recovery;
+        recovery.soul_key = ctx.accounts.soul_key.key();
+        recovery.bump = ctx.bumps.recovery;
+        recovery.configure(guardians.clone(), threshold);
+
+        emit!(RecoveryGuardiansUpdated {
+            soul_key: recovery.soul_key,
+            guardians,
+            threshold,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_recovery_guardians(
+        ctx: Context<OwnerRecoveryAction>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        SoulKeyRecovery::validate_guardians(&ctx.accounts.owner.key(), &guardians, threshold)?;
+
+        let recovery = &mut ctx.accounts.recovery;
+        recovery.queue_guardians(guardians.clone(), threshold, &Clock::get()?)?;
+
+        emit!(RecoveryGuardiansQueued {
+            soul_key: recovery.soul_key,
+            guardians,
+            threshold,
+            effective_at: recovery.guardians_effective_at,
+        });
+
+        Ok(())
+    }
+
+    pub fn apply_recovery_guardians(ctx: Context<OwnerRecoveryAction>) -> Result<()> {
+        let recovery = &mut ctx.accounts.recovery;
+        recovery.apply_guardians(&Clock::get()?)?;
+
+        emit!(RecoveryGuardiansUpdated {
+            soul_key: recovery.soul_key,
+            guardians: recovery.guardians.clone(),
+            threshold: recovery.threshold,
+        });
+
+        Ok(())
+    }
+
+    pub fn initiate_recovery(ctx: Context<GuardianRecoveryAction>, new_owner: Pubkey) -> Result<()> {
+        let clock = Clock::get()?;
+        let recovery = &mut ctx.accounts.recovery;
+        recovery.initiate(ctx.accounts.guardian.key(), new_owner, &clock)?;
+
+        emit!(RecoveryInitiated {
+            soul_key: recovery.soul_key,
+            guardian: ctx.accounts.guardian.key(),
+            new_owner,
+            executable_at: recovery.executable_at,
+        });
+
+        Ok(())
+    }
+
+    pub fn approve_recovery(ctx: Context<GuardianRecoveryAction>) -> Result<()> {
+        let recovery = &mut ctx.accounts.recovery;
+        recovery.approve(ctx.accounts.guardian.key())?;
+
+        emit!(RecoveryApproved {
+            soul_key: recovery.soul_key,
+            guardian: ctx.accounts.guardian.key(),
+            approvals: recovery.approvals.len() as u8,
+            threshold: recovery.threshold,
+        });
+
+        Ok(())
+    }
+
+    // The current owner can cancel a recovery during the timelock; once the
+    // guardians reached their quorum this is a veto they can override
+    pub fn cancel_recovery(ctx: Context<OwnerRecoveryAction>) -> Result<()> {
+        let recovery = &mut ctx.accounts.recovery;
+        if recovery.cancel(&Clock::get()?)? {
+            emit!(RecoveryCancelled {
+                soul_key: recovery.soul_key,
+            });
+        } else {
+            emit!(RecoveryVetoed {
+                soul_key: recovery.soul_key,
+                executable_at: recovery.executable_at,
+            });
+        }
+
+        Ok(())
+    }
+
+    // Permissionless once approved and unlocked: the SoulKey and its recovery
+    // settings are re-created at the new owner's PDAs and the old ones closed.
+    // The old owner's Worker moves to the new owner and the old soulbound NFT
+    // is retired; the new owner mints a fresh one from the rotated SoulKey.
+    // Open vesting escrows, passed as remaining accounts, pay the new owner,
+    // and the old witness registration is closed.
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        let clock = Clock::get()?;
+        let new_owner = ctx.accounts.old_recovery.ready_owner(&clock)?;
+        let old_owner = ctx.accounts.old_soul_key.owner;
+
+        // Stake, pending tokens and work history follow the reputation
+        let old_worker_info = ctx.accounts.old_worker.to_account_info();
+        let worker_migrated = old_worker_info.owner == &crate::ID && !old_worker_info.data_is_empty();
+        let mut vesting_count = 0;
+        if worker_migrated {
+            let new_worker = ctx
+                .accounts
+                .new_worker
+                .as_mut()
+                .ok_or(CProtocolError::RecoveryWorkerRequired)?;
+            let old_worker = decode_worker(&old_worker_info.try_borrow_data()?)?;
+            let retained = transfer_worker_state(old_worker, new_worker)?;
+            retained.try_serialize(&mut &mut old_worker_info.try_borrow_mut_data()?[..])?;
+            vesting_count = retained.vesting_count;
+
+            let mut data = Vec::new();
+            Worker::clone(new_worker).try_serialize(&mut data)?;
+            grow_account(
+                &new_worker.to_account_info(),
+                data.len(),
+                &ctx.accounts.payer.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+
+        // Escrows keep their addresses but release to the new owner
+        let vesting_escrows_moved =
+            hand_over_vesting_escrows(ctx.remaining_accounts, &old_owner, &new_owner, vesting_count)?;
+
+        // The old registration's SoulKey is about to close, so its weight
+        // could never be refreshed again; the new owner re-registers
+        let registration_info = ctx.accounts.old_witness_registration.to_account_info();
+        let witness_deregistered = registration_info.owner == &crate::ID && !registration_info.data_is_empty();
+        if witness_deregistered {
+            let registration = WitnessRegistration::try_deserialize(&mut &registration_info.try_borrow_data()?[..])?;
+            ctx.accounts.protocol_state.deregister_witness(&registration);
+            close_program_account(&registration_info, &ctx.accounts.payer.to_account_info())?;
+        }
+
+        let old_soul_key_key = ctx.accounts.old_soul_key.key();
+        let old_soul_key_signer: &[&[&[u8]]] = &[&[
+            b"soul_key",
+            old_owner.as_ref(),
+            &[ctx.bumps.old_soul_key],
+        ]];
+        let nft_burned = retire_soul_nft(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.old_soul_mint.to_account_info(),
+            &ctx.accounts.old_soul_token_account.to_account_info(),
+            &ctx.accounts.old_soul_key.to_account_info(),
+            old_soul_key_signer,
+        )?;
+
+        let mut soul_key = SoulKey::clone(&ctx.accounts.old_soul_key);
+        soul_key.rotate_owner(new_owner, &clock);
+        ctx.accounts.new_soul_key.set_inner(soul_key);
+
+        let new_soul_key = ctx.accounts.new_soul_key.key();
+        let old_recovery = &ctx.accounts.old_recovery;
+        let new_recovery = &mut ctx.accounts.new_recovery;
+        new_recovery.soul_key = new_soul_key;
+        new_recovery.bump = ctx.bumps.new_recovery;
+        new_recovery.configure(old_recovery.guardians.clone(), old_recovery.threshold);
+
+        emit!(OwnerRotated {
+            old_owner,
+            new_owner,
+            old_soul_key: old_soul_key_key,
+            new_soul_key,
+            rotations: ctx.accounts.new_soul_key.owner_rotations,
+            worker_migrated,
+            nft_burned,
+            vesting_escrows_moved,
+            witness_deregistered,
+        });
+
+        Ok(())
+    }
+
+    pub fn expand_soul_key_history(ctx: Context<ExpandSoulKeyHistory>, new_capacity: u16) -> Result<()> {
+        let soul_key = &mut ctx.accounts.soul_key;
+        let old_capacity = soul_key.evolution_capacity;
+        soul_key.grow_evolution_capacity(new_capacity)?;
+
+        emit!(SoulKeyHistoryExpanded {
+            soul_key: soul_key.key(),
+            old_capacity,
+            new_capacity,
+            account_size: SoulKey::space_for(new_capacity) as u32,
+        });
+
+        Ok(())
+    }
+
+    pub fn mint_soul_key_nft(ctx: Context<MintSoulKeyNft>, uri: String) -> Result<()> {
+        require!(uri.len() <= MAX_SOUL_NFT_URI_LEN, CProtocolError::SoulNftUriTooLong);
+
+        let owner_key = ctx.accounts.owner.key();
+        let soul_key_key = ctx.accounts.soul_key.key();
+        let soul_key_signer: &[&[&[u8]]] = &[&[
+            b"soul_key",
+            owner_key.as_ref(),
+            &[ctx.bumps.soul_key],
+        ]];
+        let soul_mint_signer: &[&[&[u8]]] = &[&[
+            b"soul_mint",
+            soul_key_key.as_ref(),
+            &[ctx.bumps.soul_mint],
+        ]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let soul_mint = ctx.accounts.soul_mint.to_account_info();
+        let soul_key_info = ctx.accounts.soul_key.to_account_info();
+
+        // Fund the mint for its largest metadata size so attribute updates never need a top-up
+        let lamports = Rent::get()?.minimum_balance(soul_mint_max_len(&uri)?);
+        let cpi_accounts = CreateAccount {
+            from: ctx.accounts.owner.to_account_info(),
+            to: soul_mint.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            cpi_accounts,
+            soul_mint_signer,
+        );
+        system_program::create_account(
+            cpi_ctx,
+            lamports,
+            soul_mint_base_len()? as u64,
+            &token_program.key(),
+        )?;
+
+        // Non-transferable supply of one, with the SoulKey PDA as mint and metadata authority
+        initialize_soul_mint_extensions(&token_program, &soul_mint, &soul_key_key)?;
+        let cpi_accounts = InitializeMint2 { mint: soul_mint.clone() };
+        let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
+        token_2022::initialize_mint2(cpi_ctx, 0, &soul_key_key, Some(&soul_key_key))?;
+
+        initialize_soul_metadata(&token_program, &soul_mint, &soul_key_info, uri, soul_key_signer)?;
+        write_soul_attributes(
+            &token_program,
+            &soul_mint,
+            &soul_key_info,
+            &ctx.accounts.soul_key,
+            soul_key_signer,
+        )?;
+
+        let cpi_accounts = associated_token::Create {
+            payer: ctx.accounts.owner.to_account_info(),
+            associated_token: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+            mint: soul_mint.clone(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: token_program.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.associated_token_program.to_account_info(), cpi_accounts);
+        associated_token::create(cpi_ctx)?;
+
+        let cpi_accounts = token_2022::MintTo {
+            mint: soul_mint.clone(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: soul_key_info,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, soul_key_signer);
+        token_2022::mint_to(cpi_ctx, 1)?;
+
+        ctx.accounts.soul_key.metadata_stale = false;
+
+        emit!(SoulKeyNftMinted {
+            soul_key: soul_key_key,
+            owner: owner_key,
+            mint: soul_mint.key(),
+        });
+
+        Ok(())
+    }
+
+    pub fn sync_soul_key_metadata(ctx: Context<SyncSoulKeyMetadata>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.soul_key.refresh_trust_epoch(&clock);
+        let owner_key = ctx.accounts.soul_key.owner;
+        let soul_key_signer: &[&[&[u8]]] = &[&[
+            b"soul_key",
+            owner_key.as_ref(),
+            &[ctx.bumps.soul_key],
+        ]];
+
+        write_soul_attributes(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.soul_mint.to_account_info(),
+            &ctx.accounts.soul_key.to_account_info(),
+            &ctx.accounts.soul_key,
+            soul_key_signer,
+        )?;
+
+        let soul_key = &mut ctx.accounts.soul_key;
+        soul_key.metadata_stale = false;
+
+        emit!(SoulKeyMetadataSynced {
+            soul_key: soul_key.key(),
+            mint: ctx.accounts.soul_mint.key(),
+            evolution_level: soul_key.evolution_level,
+            badge_count: soul_key.badge_count() as u8,
+        });
+
+        Ok(())
+    }
+
+    pub fn record_collaborative_work<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordCollaborativeWork<'info>>,
+        work_type: WorkType,
+        effort_weight: u64,
+        task_metadata: String,
+        splits: Vec<CollaboratorSplit>,
+    ) -> Result<()> {
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let initiator = ctx.accounts.authority.key();
+
+        require!(
+            ctx.accounts.worker.staked_amount >= protocol_state.min_stake_to_emit,
+            CProtocolError::InsufficientStake
+        );
+
+        let clock = Clock::get()?;
+        let collaboration = &mut ctx.accounts.collaborative_work;
+        collaboration.set_inner(CollaborativeWork::new(
+            initiator,
+            work_type,
+            effort_weight,
+            hash_string(&task_metadata),
+            &splits,
+            &clock,
+            ctx.bumps.collaborative_work,
+        )?);
+
+        // Contributors are passed as (worker, soul_key) pairs in split order
+        require!(
+            ctx.remaining_accounts.len() == splits.len() * 2,
+            CProtocolError::CollaboratorAccountMismatch
+        );
+
+        protocol_state.total_work_recorded += 1;
+
+        for (split, accounts) in splits.iter().zip(ctx.remaining_accounts.chunks(2)) {
+            let worker_info = &accounts[0];
+            require_keys_eq!(
+                worker_info.key(),
+                split.worker,
+                CProtocolError::CollaboratorAccountMismatch
+            );
+
+            // Contributors who did not sign this transaction accept later
+            if !worker_info.is_signer {
+                continue;
+            }
+
+            let mut worker = Account::<Worker>::try_from(worker_info)?;
+            let mut soul_key = Account::<SoulKey>::try_from(&accounts[1])?;
+            require_keys_eq!(
+                soul_key.owner,
+                worker_info.key(),
+                CProtocolError::SoulKeyOwnerMismatch
+            );
+
+            let (share, effort_points) = credit_collaborator(
+                split.worker,
+                &mut worker,
+                &mut soul_key,
+                protocol_state,
+                ctx.accounts.reward_epoch.as_deref_mut(),
+                collaboration,
+                &clock,
+            )?;
+
+            emit!(CollaboratorCredited {
+                collaboration: collaboration.key(),
+                worker: worker_info.key(),
+                share_bps: share.share_bps,
+                emission_amount: share.emission_amount,
+                effort_points,
+            });
+
+            worker.exit(&crate::ID)?;
+            soul_key.exit(&crate::ID)?;
+        }
+
+        emit!(CollaborativeWorkRecorded {
+            collaboration: collaboration.key(),
+            initiator,
+            work_type,
+            effort_weight,
+            total_emission: collaboration.total_emission,
+            contributor_count: collaboration.contributors.len() as u8,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn accept_collaborative_work(ctx: Context<AcceptCollaborativeWork>) -> Result<()> {
+        let clock = Clock::get()?;
+        let (share, effort_points) = credit_collaborator(
+            ctx.accounts.authority.key(),
+            &mut ctx.accounts.worker,
+            &mut ctx.accounts.soul_key,
+            &mut ctx.accounts.protocol_state,
+            ctx.accounts.reward_epoch.as_deref_mut(),
+            &mut ctx.accounts.collaborative_work,
+            &clock,
+        )?;
+
+        emit!(CollaboratorCredited {
+            collaboration: ctx.accounts.collaborative_work.key(),
+            worker: ctx.accounts.worker.key(),
+            share_bps: share.share_bps,
+            emission_amount: share.emission_amount,
+            effort_points,
+        });
+
+        Ok(())
+    }
+
+    pub fn create_bounty(
+        ctx: Context<CreateBounty>,
+        bounty_id: u64,
+        terms: BountyTerms,
+    ) -> Result<()> {
+        terms.validate()?;
+
+        // Escrow the reward up front so payout never depends on the sponsor
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.sponsor_token_account.to_account_info(),
+            to: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.sponsor.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, terms.reward_amount)?;
+
+        let clock = Clock::get()?;
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.set_inner(Bounty {
+            sponsor: ctx.accounts.sponsor.key(),
+            bounty_id,
+            reward_mint: ctx.accounts.reward_mint.key(),
+            reward_amount: terms.reward_amount,
+            work_type: terms.work_type,
+            effort_weight: terms.effort_weight,
+            description: terms.description,
+            reviewers: terms.reviewers,
+            approval_threshold: terms.approval_threshold,
+            approvals: Vec::new(),
+            claimant: None,
+            status: BountyStatus::Open,
+            created_timestamp: clock.unix_timestamp,
+            claimed_timestamp: 0,
+            bump: ctx.bumps.bounty,
+            escrow_bump: ctx.bumps.escrow,
+        });
+
+        emit!(BountyCreated {
+            bounty: bounty.key(),
+            sponsor: bounty.sponsor,
+            reward_mint: bounty.reward_mint,
+            reward_amount: bounty.reward_amount,
+            work_type: bounty.work_type,
+        });
+
+        Ok(())
+    }
+
+    // A claim lapses after BOUNTY_CLAIM_WINDOW, after which anyone may claim
+    // the bounty again or the sponsor may cancel it
+    pub fn claim_bounty(ctx: Context<ClaimBounty>) -> Result<()> {
+        require!(
+            ctx.accounts.worker.staked_amount >= ctx.accounts.protocol_state.min_stake_to_emit,
+            CProtocolError::InsufficientStake
+        );
+
+        let clock = Clock::get()?;
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.claim(ctx.accounts.authority.key(), &clock)?;
+
+        emit!(BountyClaimed {
+            bounty: bounty.key(),
+            claimant: ctx.accounts.authority.key(),
+            claim_deadline: bounty.claim_deadline(),
+        });
+
+        Ok(())
+    }
+
+    // The claimant never approves their own claim, even when listed as a reviewer
+    pub fn approve_bounty(ctx: Context<ApproveBounty>) -> Result<()> {
+        let clock = Clock::get()?;
+        let bounty = &mut ctx.accounts.bounty;
+        let quorum_reached = bounty.record_approval(ctx.accounts.reviewer.key(), &clock)?;
+
+        emit!(BountyApproved {
+            bounty: bounty.key(),
+            reviewer: ctx.accounts.reviewer.key(),
+            approvals: bounty.approvals.len() as u8,
+            quorum_reached,
+        });
+
+        if !quorum_reached {
+            return Ok(());
+        }
+
+        // Quorum reached: release the escrow and record the work in the same instruction
+        let sponsor = bounty.sponsor;
+        let bounty_id_bytes = bounty.bounty_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"bounty",
+            sponsor.as_ref(),
+            &bounty_id_bytes,
+            &[bounty.bump],
+        ]];
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.escrow.to_account_info(),
+            to: ctx.accounts.claimant_token_account.to_account_info(),
+            authority: bounty.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, bounty.reward_amount)?;
+
+        // The emptied escrow's rent goes back to the sponsor who funded it
+        let cpi_accounts = token::CloseAccount {
+            account: ctx.accounts.escrow.to_account_info(),
+            destination: ctx.accounts.sponsor.to_account_info(),
+            authority: bounty.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::close_account(cpi_ctx)?;
+
+        // Bounty work passes the same stake and task cooldown checks as record_work
+        let protocol_state = &mut ctx.accounts.protocol_state;
+        let claimant_worker = &mut ctx.accounts.claimant_worker;
+        require!(
+            claimant_worker.staked_amount >= protocol_state.min_stake_to_emit,
+            CProtocolError::InsufficientStake
+        );
+        let task_hash = hash_task(&bounty.work_type, &bounty.description, claimant_worker.key());
+        track_task(claimant_worker, task_hash, get_cooldown(&bounty.work_type), &clock)?;
+
+        let emission_amount = calculate_emission(&bounty.work_type, bounty.effort_weight)?;
+        let record = WorkRecord {
+            work_type: bounty.work_type,
+            effort_weight: bounty.effort_weight,
+            timestamp: clock.unix_timestamp,
+            emission_amount,
+            metadata_hash: hash_string(&bounty.description),
+            organization: None,
+            unminted_amount: emission_amount,
+        };
+        let effort_points = accrue_effort(
+            protocol_state,
+            ctx.accounts.reward_epoch.as_deref_mut(),
+            Some(&ctx.accounts.claimant_soul_key),
+            emission_amount,
+            &clock,
+        )?;
+        ctx.accounts.claimant_soul_key.update_after_work(&record, protocol_state.streak_break_days, &clock)?;
+        push_work_record(claimant_worker, record, protocol_state);
+
+        protocol_state.total_work_recorded += 1;
+        protocol_state.total_tokens_emitted += emission_amount;
+
+        bounty.status = BountyStatus::Completed;
+
+        emit!(BountyPaid {
+            bounty: bounty.key(),
+            claimant: ctx.accounts.claimant_worker.key(),
+            reward_mint: bounty.reward_mint,
+            reward_amount: bounty.reward_amount,
+            emission_amount,
+            effort_points,
+        });
+
+        Ok(())
+    }
+
+    pub fn cancel_bounty(ctx: Context<CancelBounty>) -> Result<()> {
+        let bounty = &ctx.accounts.bounty;
+        require!(
+            bounty.is_cancellable(&Clock::get()?),
+            CProtocolError::BountyNotCancellable
+        );
+
+        // Refund the sponsor and close the escrow; the bounty account is closed by Anchor
+        let sponsor = bounty.sponsor;
+        let bounty_id_bytes = bounty.bounty_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"bounty",
+            sponsor.as_ref(),
+            &bounty_id_bytes,
+            &[bounty.bump],
+        ]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.escrow.to_account_info(),
+            to: ctx.accounts.sponsor_token_account.to_account_info(),
+            authority: bounty.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, ctx.accounts.escrow.amount)?;
+
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.escrow.to_account_info(),
+            destination: ctx.accounts.sponsor.to_account_info(),
+            authority: bounty.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::close_account(cpi_ctx)?;
+
+        emit!(BountyCancelled {
+            bounty: bounty.key(),
+            sponsor,
+            refunded_amount: bounty.reward_amount,
+        });
+
+        Ok(())
+    }
+
+    // Organizations mint against protocol emissions, so the protocol authority
+    // approves each one and grants its budget
+    pub fn create_organization(
+        ctx: Context<CreateOrganization>,
+        organization_id: u64,
+        name: String,
+        admins: Vec<Pubkey>,
+        emission_budget: u64,
+    ) -> Result<()> {
+        Organization::validate_profile(&name, &admins)?;
+
+        let organization = &mut ctx.accounts.organization;
+        organization.set_inner(Organization {
+            creator: ctx.accounts.creator.key(),
+            organization_id,
+            name,
+            admins,
+            members: Vec::new(),
+            work_type_configs: Vec::new(),
+            emission_budget,
+            emission_used: 0,
+            total_work_vouched: 0,
+            created_timestamp: Clock::get()?.unix_timestamp,
+            bump: ctx.bumps.organization,
+        });
+
+        emit!(OrganizationCreated {
+            organization: organization.key(),
+            creator: organization.creator,
+            emission_budget,
+        });
+
+        Ok(())
+    }
+
+    pub fn add_organization_member(ctx: Context<ManageOrganization>, member: Pubkey) -> Result<()> {
+        let organization = &mut ctx.accounts.organization;
+        organization.require_admin(&ctx.accounts.admin.key())?;
+        organization.add_member(member)?;
+
+        emit!(OrganizationMemberUpdated {
+            organization: organization.key(),
+            member,
+            added: true,
+        });
+
+        Ok(())
+    }
+
+    pub fn remove_organization_member(ctx: Context<ManageOrganization>, member: Pubkey) -> Result<()> {
+        let organization = &mut ctx.accounts.organization;
+        organization.require_admin(&ctx.accounts.admin.key())?;
+        organization.remove_member(&member)?;
+
+        emit!(OrganizationMemberUpdated {
+            organization: organization.key(),
+            member,
+            added: false,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_work_type_config(ctx: Context<ManageOrganization>, config: WorkTypeConfig) -> Result<()> {
+        let organization = &mut ctx.accounts.organization;
+        organization.require_admin(&ctx.accounts.admin.key())?;
+        Organization::validate_work_type_config(&config)?;
+        if let Some(schedule) = config.vesting {
+            emit!(VestingScheduleUpdated {
+                organization: Some(organization.key()),
+                work_type: config.work_type,
+                schedule,
+            });
+        }
+        organization.upsert_work_type_config(config);
+
+        emit!(WorkTypeConfigUpdated {
+            organization: organization.key(),
+            work_type: config.work_type,
+            base_emission: config.base_emission,
+            cooldown: config.cooldown,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_organization_budget(ctx: Context<SetOrganizationBudget>, emission_budget: u64) -> Result<()> {
+        let organization = &mut ctx.accounts.organization;
+        require!(
+            emission_budget >= organization.emission_used,
+            CProtocolError::OrganizationBudgetExceeded
+        );
+        organization.emission_budget = emission_budget;
+
+        emit!(OrganizationBudgetUpdated {
+            organization: organization.key(),
+            emission_budget,
+            emission_used: organization.emission_used,
+        });
+
+        Ok(())
+    }
+
+    pub fn record_organization_work(
+        ctx: Context<RecordOrganizationWork>,
+        work_type: WorkType,
+        effort_weight: u64,
+        task_metadata: String,
+    ) -> Result<()> {
+        let organization = &mut ctx.accounts.organization;
+        let worker = &mut ctx.accounts.worker;
+        let protocol_state = &mut ctx.accounts.protocol_state;
+
+        // The organization vouches for the work: an admin co-signs for one of its members
+        organization.require_admin(&ctx.accounts.verifier.key())?;
+        require!(
+            organization.is_member(&worker.key()),
+            CProtocolError::NotAnOrganizationMember
+        );
+        require!(
+            worker.staked_amount >= protocol_state.min_stake_to_emit,
+            CProtocolError::InsufficientStake
+        );
+
+        let clock = Clock::get()?;
+        let task_hash = hash_task(&work_type, &task_metadata, worker.key());
+        track_task(worker, task_hash, organization.cooldown(&work_type), &clock)?;
+
+        // Organization overrides take precedence and draw down its emission budget
+        let emission_amount = organization.calculate_emission(&work_type, effort_weight)?;
+        organization.consume_budget(emission_amount)?;
+        let effort_points = accrue_effort(
+            protocol_state,
+            ctx.accounts.reward_epoch.as_deref_mut(),
+            ctx.accounts.soul_key.as_deref(),
+            emission_amount,
+            &clock,
+        )?;
+
+        let record = WorkRecord {
+            work_type,
+            effort_weight,
+            timestamp: clock.unix_timestamp,
+            emission_amount,
+            metadata_hash: hash_string(&task_metadata),
+            organization: Some(organization.key()),
+            unminted_amount: emission_amount,
+        };
+        if let Some(soul_key) = ctx.accounts.soul_key.as_deref_mut() {
+            soul_key.update_after_work(&record, protocol_state.streak_break_days, &clock)?;
+        }
+        push_work_record(worker, record, protocol_state);
+
+        protocol_state.total_work_recorded += 1;
+        protocol_state.total_tokens_emitted += emission_amount;
+
+        emit!(OrganizationWorkRecorded {
+            organization: organization.key(),
+            worker: worker.key(),
+            verifier: ctx.accounts.verifier.key(),
+            work_type,
+            effort_weight,
+            emission_amount,
+            effort_points,
+            budget_remaining: organization.emission_budget - organization.emission_used,
+        });
+
+        Ok(())
+    }
+
+    pub fn apply_decay(ctx: Context<ApplyDecay>) -> Result<()> {
+        let worker = &mut ctx.accounts.worker;
+        let protocol_state = &ctx.accounts.protocol_state;
+        let clock = Clock::get()?;
+
+        // Calculate time since last activity
+        let time_inactive = clock.unix_timestamp - worker.last_activity_timestamp;
+        let epochs_inactive = time_inactive / 86400; // 1 day = 1 epoch
+
+        if epochs_inactive > 0 {
+            // Apply exponential decay
+            let decay_multiplier = protocol_state.decay_rate as u64;
+            let decay_amount = worker.pending_tokens
+                .checked_mul(decay_multiplier)
+                .ok_or(CProtocolError::MathOverflow)?
+                .checked_mul(epochs_inactive as u64)
+                .ok_or(CProtocolError::MathOverflow)?
+                .checked_div(10000)
+                .ok_or(CProtocolError::MathOverflow)?;
+
+            worker.pending_tokens = worker.pending_tokens.saturating_sub(decay_amount);
+            worker.total_tokens_decayed += decay_amount;
+
+            emit!(TokensDecayed {
+                worker: worker.key(),
+                decay_amount,
+                remaining_balance: worker.pending_tokens,
+                epochs_inactive: epochs_inactive as u64,
+            });
+        }
+
+        worker.last_decay_check = clock.unix_timestamp;
+        Ok(())
+    }
+
+    // Mints one recorded piece of work. The amount, work type, organization
+    // and so the vesting schedule all come from the WorkRecord, never the caller.
+    pub fn mint_with_consensus(
+        ctx: Context<MintWithConsensus>,
+        record_index: u32,
+        witness_signatures: Vec<WitnessSignature>,
+    ) -> Result<()> {
+        let protocol_state = &ctx.accounts.protocol_state;
+        let record = ctx
+            .accounts
+            .worker
+            .work_history
+            .get(record_index as usize)
+            .cloned()
+            .ok_or(CProtocolError::InvalidWorkRecord)?;
+        let amount = record.unminted_amount;
+        require!(amount > 0, CProtocolError::WorkRecordAlreadyMinted);
+        
+        // Verify witness consensus; every witness counts once
+        require!(!witness_signatures.is_empty(), CProtocolError::InsufficientConsensus);
+        for (index, signature) in witness_signatures.iter().enumerate() {
+            require!(
+                !witness_signatures[..index]
+                    .iter()
+                    .any(|other| other.witness_pubkey == signature.witness_pubkey),
+                CProtocolError::InvalidWitnessSignature
+            );
+        }
+        let total_weight = witness_signatures
+            .iter()
+            .try_fold(0u64, |total, w| total.checked_add(w.weight))
+            .ok_or(CProtocolError::MathOverflow)?;
+        require!(
+            total_weight >= protocol_state.witness_quorum()?,
+            CProtocolError::InsufficientConsensus
+        );
+
+        // Verify each signature
+        for signature in &witness_signatures {
+            require!(
+                verify_witness_signature(signature, amount, ctx.accounts.worker.key()),
+                CProtocolError::InvalidWitnessSignature
+            );
+        }
+
+        // Work an organization vouched for takes its schedule, if it overrides one
+        let organization_schedule = match (record.organization, &ctx.accounts.organization) {
+            (None, None) => None,
+            (Some(vouched_by), Some(organization)) if vouched_by == organization.key() => {
+                organization.vesting_schedule(&record.work_type)
+            }
+            _ => return err!(CProtocolError::OrganizationMismatch),
+        };
+        let schedule = organization_schedule
+            .unwrap_or(protocol_state.vesting_schedules[record.work_type as usize]);
+
+        let destination = reward_destination(
+            &schedule,
+            &ctx.accounts.worker_token_account,
+            &ctx.accounts.vesting_escrow,
+            &ctx.accounts.vesting_vault,
+        )?;
+
+        let protocol_state_key = protocol_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            PROTOCOL_AUTHORITY_SEED,
+            protocol_state_key.as_ref(),
+            &[ctx.bumps.protocol_authority],
+        ]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.c_token_mint.to_account_info(),
+            to: destination,
+            authority: ctx.accounts.protocol_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::mint_to(cpi_ctx, amount)?;
+
+        if let Some(vesting_escrow) = &mut ctx.accounts.vesting_escrow {
+            open_vesting_escrow(
+                vesting_escrow,
+                &mut ctx.accounts.worker,
+                schedule,
+                amount,
+                ctx.bumps.vesting_escrow,
+                ctx.bumps.vesting_vault,
+            )?;
+        }
+
+        // Update worker pending tokens
+        let worker = &mut ctx.accounts.worker;
+        worker.work_history[record_index as usize].unminted_amount = 0;
+        worker.pending_tokens = worker.pending_tokens.saturating_sub(amount);
+        worker.total_tokens_minted += amount;
+
+        emit!(TokensMinted {
+            worker: worker.key(),
+            amount,
+            witness_count: witness_signatures.len() as u8,
+            consensus_weight: total_weight,
+        });
+
+        Ok(())
+    }
+
+    pub fn release_vested(ctx: Context<ReleaseVested>) -> Result<()> {
+        let clock = Clock::get()?;
+        let vesting_escrow = &mut ctx.accounts.vesting_escrow;
+        let amount = vesting_escrow.take_releasable(&clock);
+        require!(amount > 0, CProtocolError::NothingToRelease);
+
+        let protocol_state_key = ctx.accounts.protocol_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            PROTOCOL_AUTHORITY_SEED,
+            protocol_state_key.as_ref(),
+            &[ctx.bumps.protocol_authority],
+        ]];
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.vesting_vault.to_account_info(),
+            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            authority: ctx.accounts.protocol_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit!(VestedTokensReleased {
+            vesting_escrow: vesting_escrow.key(),
+            beneficiary: vesting_escrow.beneficiary,
+            amount,
+            released_amount: vesting_escrow.released_amount,
+        });
+
+        Ok(())
+    }
+
+    // Releases everything now: vested tokens in full, the unvested remainder
+    // minus the penalty, which goes to the decay destination. Both the vault
+    // and the escrow are closed.
+    pub fn exit_vesting(ctx: Context<ExitVesting>) -> Result<()> {
+        let clock = Clock::get()?;
+        let vesting_escrow = &mut ctx.accounts.vesting_escrow;
+        let vested = vesting_escrow.take_releasable(&clock);
+        let (unvested_payout, penalty) = vesting_escrow.exit_split();
+
+        let protocol_state_key = ctx.accounts.protocol_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            PROTOCOL_AUTHORITY_SEED,
+            protocol_state_key.as_ref(),
+            &[ctx.bumps.protocol_authority],
+        ]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.vesting_vault.to_account_info(),
+            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            authority: ctx.accounts.protocol_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, vested + unvested_payout)?;
+
+        if penalty > 0 {
+            let cpi_accounts = token::Transfer {
+                from: ctx.accounts.vesting_vault.to_account_info(),
+                to: ctx.accounts.decay_destination.to_account_info(),
+                authority: ctx.accounts.protocol_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, penalty)?;
+        }
+
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.vesting_vault.to_account_info(),
+            destination: ctx.accounts.beneficiary.to_account_info(),
+            authority: ctx.accounts.protocol_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::close_account(cpi_ctx)?;
+
+        emit!(VestingExited {
+            vesting_escrow: vesting_escrow.key(),
+            beneficiary: vesting_escrow.beneficiary,
+            released_amount: vested + unvested_payout,
+            penalty,
+        });
+
+        Ok(())
+    }
+
+    pub fn stake_to_emit(ctx: Context<StakeToEmit>, amount: u64) -> Result<()> {
+        let worker = &mut ctx.accounts.worker;
+        
+        // Transfer stake to protocol
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.worker_token_account.to_account_info(),
+            to: ctx.accounts.protocol_vault.to_account_info(),
+            authority: ctx.accounts.worker_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        worker.staked_amount += amount;
+        worker.stake_timestamp = Clock::get()?.unix_timestamp;
+
+        emit!(WorkerStaked {
+            worker: worker.key(),
+            amount,
+            total_staked: worker.staked_amount,
+        });
+
+        Ok(())
+    }
+
+    // Rewrites a Worker still in the pre-organization layout, growing the
+    // account to fit the wider work records
+    pub fn migrate_worker(ctx: Context<MigrateWorker>) -> Result<()> {
+        let worker_info = ctx.accounts.worker.to_account_info();
+        let worker = LegacyWorker::try_from_account_data(&worker_info.try_borrow_data()?)?.into_worker();
+
+        let mut data = Vec::new();
+        worker.try_serialize(&mut data)?;
+        grow_account(
+            &worker_info,
+            data.len(),
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        worker_info.try_borrow_mut_data()?[..data.len()].copy_from_slice(&data);
+
+        emit!(WorkerMigrated {
+            worker: worker_info.key(),
+            work_records: worker.work_history.len() as u32,
+            pending_tokens: worker.pending_tokens,
+        });
+
+        Ok(())
+    }
+
+    // Rewrites a ProtocolState created by the original initialize_protocol.
+    // That layout has no authority, so the program's upgrade authority signs
+    // and becomes the protocol authority
+    pub fn migrate_protocol_state(ctx: Context<MigrateProtocolState>) -> Result<()> {
+        let state_info = ctx.accounts.protocol_state.to_account_info();
+        let authority = ctx.accounts.authority.key();
+        let protocol_state = LegacyProtocolState::try_from_account_data(&state_info.try_borrow_data()?)?
+            .into_protocol_state(authority);
+
+        let mut data = Vec::new();
+        protocol_state.try_serialize(&mut data)?;
+        grow_account(
+            &state_info,
+            8 + ProtocolState::INIT_SPACE,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        state_info.try_borrow_mut_data()?[..data.len()].copy_from_slice(&data);
+
+        emit!(ProtocolStateMigrated {
+            protocol_state: state_info.key(),
+            authority,
+            total_work_recorded: protocol_state.total_work_recorded,
+        });
+
+        Ok(())
+    }
+}
+
+// Data Structures
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, InitSpace)]
+pub enum WorkType {
+    OnboardUser,
+    CreateContent,
+    WriteCode,
+    ReferClient,
+    CloseDeal,
+    CommunityManagement,
+    BugReport,
+    Documentation,
+    Marketing,
+    UserSupport,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct WorkRecord {
+    pub work_type: WorkType,
+    pub effort_weight: u64,
+    pub timestamp: i64,
+    pub emission_amount: u64,
+    pub metadata_hash: u64,
+    pub organization: Option<Pubkey>, // Organization that vouched for the work
+    pub unminted_amount: u64, // Credited to pending_tokens and not yet minted
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WitnessSignature {
+    pub witness_pubkey: Pubkey,
+    pub weight: u64, // Voting power
+    pub signature: [u8; 64],
+    pub timestamp: i64,
+}
+
+// Account Structures
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolState {
+    pub total_work_recorded: u64,
+    pub total_tokens_emitted: u64,
+    pub decay_rate: u16, // Basis points per epoch
+    pub witness_threshold: u64, // Basis points of total_witness_trust needed for consensus
+    pub min_stake_to_emit: u64,
+    pub authority: Pubkey,
+    pub streak_break_days: u16, // Days without activity before a streak resets
+    pub attestor: Pubkey, // Off-chain key that signs reputation attestations
+    pub c_token_mint: Pubkey,
+    pub epoch_emission: u64, // C tokens distributed per reward epoch
+    pub epoch_duration: i64, // Seconds; zero until reward epochs start
+    pub current_epoch: u64,
+    pub decay_destination: Pubkey, // C token account receiving early-exit vesting penalties
+    pub vesting_schedules: [VestingSchedule; WORK_TYPE_COUNT], // Indexed by WorkType
+    pub total_witness_trust: u64, // Sum of WitnessRegistration weights
+}
+
+#[account]
+#[derive(Default)]
+pub struct Worker {
+    pub total_work_completed: u64,
+    pub last_work_timestamp: i64,
+    pub last_activity_timestamp: i64,
+    pub last_decay_check: i64,
+    pub pending_tokens: u64,
+    pub total_tokens_minted: u64,
+    pub total_tokens_decayed: u64,
+    pub staked_amount: u64,
+    pub stake_timestamp: i64,
+    pub work_diversity_score: u64,
+    pub work_history: Vec<WorkRecord>,
+    pub recent_tasks: Vec<u64>, // Task hashes for anti-gaming
+    pub vesting_count: u64, // Vesting escrows created so far; seeds the next one
+}
+
+// Context Structures
+#[derive(Accounts)]
+pub struct InitializeProtocol<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProtocolState::INIT_SPACE
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        has_one = authority @ CProtocolError::UnauthorizedAuthority
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordWork<'info> {
+    #[account(
+        mut,
+        constraint = worker.key() == authority.key()
+    )]
+    pub worker: Account<'info, Worker>,
+    #[account(mut)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    // Both required once reward epochs are live; see accrue_effort. A passed
+    // SoulKey records the work either way.
+    #[account(
+        mut,
+        seeds = [b"soul_key", authority.key().as_ref()],
+        bump
+    )]
+    pub soul_key: Option<Account<'info, SoulKey>>,
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &protocol_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Option<Account<'info, RewardEpoch>>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StartRewardEpochs<'info> {
+    #[account(
+        mut,
+        has_one = authority @ CProtocolError::UnauthorizedAuthority
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub c_token_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RewardEpoch::INIT_SPACE,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &0u64.to_le_bytes()],
+        bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRewardEpoch<'info> {
+    #[account(mut)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &protocol_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RewardEpoch::INIT_SPACE,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &(protocol_state.current_epoch + 1).to_le_bytes()],
+        bump
+    )]
+    pub next_epoch: Account<'info, RewardEpoch>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct PostEpochRewardsRoot<'info> {
+    #[account(has_one = authority @ CProtocolError::UnauthorizedAuthority)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimEpochRewards<'info> {
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &reward_epoch.epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + EpochClaim::INIT_SPACE,
+        seeds = [b"epoch_claim", reward_epoch.key().as_ref(), claimant.key().as_ref()],
+        bump
+    )]
+    pub epoch_claim: Account<'info, EpochClaim>,
+    // Numbers the claimant's vesting escrows
+    #[account(mut, address = claimant.key())]
+    pub worker: Account<'info, Worker>,
+    #[account(
+        mut,
+        token::mint = c_token_mint,
+        token::authority = claimant
+    )]
+    pub claimant_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = protocol_state.c_token_mint
+    )]
+    pub c_token_mint: Account<'info, Mint>,
+    /// CHECK: Mint authority PDA; only signs
+    #[account(
+        seeds = [PROTOCOL_AUTHORITY_SEED, protocol_state.key().as_ref()],
+        bump
+    )]
+    pub protocol_authority: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + VestingEscrow::INIT_SPACE,
+        seeds = [b"vesting", worker.key().as_ref(), &worker.vesting_count.to_le_bytes()],
+        bump
+    )]
+    pub vesting_escrow: Option<Account<'info, VestingEscrow>>,
+    #[account(
+        init,
+        payer = claimant,
+        token::mint = c_token_mint,
+        token::authority = protocol_authority,
+        seeds = [b"vesting_vault", worker.key().as_ref(), &worker.vesting_count.to_le_bytes()],
+        bump
+    )]
+    pub vesting_vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterWitness<'info> {
+    #[account(mut)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"soul_key", witness.key().as_ref()],
+        bump
+    )]
+    pub witness_soul_key: Account<'info, SoulKey>,
+    #[account(
+        init,
+        payer = witness,
+        space = 8 + WitnessRegistration::INIT_SPACE,
+        seeds = [WITNESS_REGISTRATION_SEED, protocol_state.key().as_ref(), witness_soul_key.key().as_ref()],
+        bump
+    )]
+    pub witness_registration: Account<'info, WitnessRegistration>,
+    #[account(mut)]
+    pub witness: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SeedWitness<'info> {
+    #[account(
+        mut,
+        has_one = authority @ CProtocolError::UnauthorizedAuthority
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"soul_key", witness_soul_key.owner.as_ref()],
+        bump
+    )]
+    pub witness_soul_key: Account<'info, SoulKey>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WitnessRegistration::INIT_SPACE,
+        seeds = [WITNESS_REGISTRATION_SEED, protocol_state.key().as_ref(), witness_soul_key.key().as_ref()],
+        bump
+    )]
+    pub witness_registration: Account<'info, WitnessRegistration>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshWitness<'info> {
+    #[account(mut)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        seeds = [b"soul_key", witness_soul_key.owner.as_ref()],
+        bump
+    )]
+    pub witness_soul_key: Account<'info, SoulKey>,
+    #[account(
+        mut,
+        seeds = [WITNESS_REGISTRATION_SEED, protocol_state.key().as_ref(), witness_soul_key.key().as_ref()],
+        bump = witness_registration.bump
+    )]
+    pub witness_registration: Account<'info, WitnessRegistration>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64, merkle_root: [u8; 32], total_amount: u64, leaf_count: u32)]
+pub struct ProposeDistribution<'info> {
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    #[account(
+        init,
+        payer = witness,
+        space = Distribution::space_for(leaf_count.min(MAX_DISTRIBUTION_LEAVES)),
+        seeds = [b"distribution", protocol_state.key().as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(
+        mut,
+        seeds = [b"soul_key", witness.key().as_ref()],
+        bump
+    )]
+    pub witness_soul_key: Account<'info, SoulKey>,
+    #[account(
+        seeds = [WITNESS_REGISTRATION_SEED, protocol_state.key().as_ref(), witness_soul_key.key().as_ref()],
+        bump = witness_registration.bump
+    )]
+    pub witness_registration: Account<'info, WitnessRegistration>,
+    #[account(mut)]
+    pub witness: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RejectDistribution<'info> {
+    #[account(has_one = authority @ CProtocolError::UnauthorizedAuthority)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        has_one = protocol_state,
+        has_one = proposer,
+        close = proposer,
+        seeds = [b"distribution", protocol_state.key().as_ref(), &distribution.epoch.to_le_bytes()],
+        bump = distribution.bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+    /// CHECK: Receives the rent it paid for the proposal
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveDistribution<'info> {
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        has_one = protocol_state,
+        seeds = [b"distribution", protocol_state.key().as_ref(), &distribution.epoch.to_le_bytes()],
+        bump = distribution.bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(
+        mut,
+        seeds = [b"soul_key", witness.key().as_ref()],
+        bump
+    )]
+    pub witness_soul_key: Account<'info, SoulKey>,
+    #[account(
+        seeds = [WITNESS_REGISTRATION_SEED, protocol_state.key().as_ref(), witness_soul_key.key().as_ref()],
+        bump = witness_registration.bump
+    )]
+    pub witness_registration: Account<'info, WitnessRegistration>,
+    #[account(
+        init,
+        payer = witness,
+        space = 8 + WitnessVote::INIT_SPACE,
+        seeds = [WITNESS_VOTE_SEED, distribution.key().as_ref(), witness_soul_key.key().as_ref()],
+        bump
+    )]
+    pub witness_vote: Account<'info, WitnessVote>,
+    #[account(mut)]
+    pub witness: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDistribution<'info> {
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        has_one = protocol_state,
+        seeds = [b"distribution", protocol_state.key().as_ref(), &distribution.epoch.to_le_bytes()],
+        bump = distribution.bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &distribution.epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Account<'info, RewardEpoch>,
+    // The leaf key and token account owner; numbers its vesting escrows
+    #[account(mut)]
+    pub worker: Account<'info, Worker>,
+    #[account(
+        mut,
+        token::mint = c_token_mint,
+        token::authority = worker
+    )]
+    pub worker_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = protocol_state.c_token_mint
+    )]
+    pub c_token_mint: Account<'info, Mint>,
+    /// CHECK: Mint authority PDA; only signs
+    #[account(
+        seeds = [PROTOCOL_AUTHORITY_SEED, protocol_state.key().as_ref()],
+        bump
+    )]
+    pub protocol_authority: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VestingEscrow::INIT_SPACE,
+        seeds = [b"vesting", worker.key().as_ref(), &worker.vesting_count.to_le_bytes()],
+        bump
+    )]
+    pub vesting_escrow: Option<Account<'info, VestingEscrow>>,
+    #[account(
+        init,
+        payer = payer,
+        token::mint = c_token_mint,
+        token::authority = protocol_authority,
+        seeds = [b"vesting_vault", worker.key().as_ref(), &worker.vesting_count.to_le_bytes()],
+        bump
+    )]
+    pub vesting_vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSoulKey<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = SoulKey::SPACE,
+        seeds = [b"soul_key", owner.key().as_ref()],
+        bump
+    )]
+    pub soul_key: Account<'info, SoulKey>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Witness outcomes are reported by the protocol authority once consensus on a claim is final
+#[derive(Accounts)]
+pub struct ReportWitnessOutcome<'info> {
+    #[account(has_one = authority @ CProtocolError::UnauthorizedAuthority)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"soul_key", witness_soul_key.owner.as_ref()],
+        bump
+    )]
+    pub witness_soul_key: Account<'info, SoulKey>,
+    // Each vote settles once; the PDA ties it to the witness's SoulKey
+    #[account(
+        mut,
+        seeds = [WITNESS_VOTE_SEED, witness_vote.subject.as_ref(), witness_soul_key.key().as_ref()],
+        bump = witness_vote.bump
+    )]
+    pub witness_vote: Account<'info, WitnessVote>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleWitnessVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"soul_key", witness_soul_key.owner.as_ref()],
+        bump
+    )]
+    pub witness_soul_key: Account<'info, SoulKey>,
+    #[account(
+        mut,
+        seeds = [WITNESS_VOTE_SEED, witness_vote.subject.as_ref(), witness_soul_key.key().as_ref()],
+        bump = witness_vote.bump
+    )]
+    pub witness_vote: Account<'info, WitnessVote>,
+    /// CHECK: The voted-on distribution, or its closed address; read by Distribution::approval_outcome
+    #[account(address = witness_vote.subject)]
+    pub subject: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AttestSoulKey<'info> {
+    #[account(
+        seeds = [b"soul_key", soul_key.owner.as_ref()],
+        bump
+    )]
+    pub soul_key: Account<'info, SoulKey>,
+}
+
+#[derive(Accounts)]
+pub struct RecordLiquidityDeposits<'info> {
+    #[account(has_one = attestor @ CProtocolError::UnauthorizedAttestor)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        seeds = [b"soul_key", soul_key.owner.as_ref()],
+        bump
+    )]
+    pub soul_key: Account<'info, SoulKey>,
+    pub attestor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRecovery<'info> {
+    #[account(
+        seeds = [b"soul_key", owner.key().as_ref()],
+        bump,
+        has_one = owner
+    )]
+    pub soul_key: Account<'info, SoulKey>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SoulKeyRecovery::INIT_SPACE,
+        seeds = [b"recovery", soul_key.key().as_ref()],
+        bump
+    )]
+    pub recovery: Account<'info, SoulKeyRecovery>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OwnerRecoveryAction<'info> {
+    #[account(
+        seeds = [b"soul_key", owner.key().as_ref()],
+        bump,
+        has_one = owner
+    )]
+    pub soul_key: Account<'info, SoulKey>,
+    #[account(
+        mut,
+        seeds = [b"recovery", soul_key.key().as_ref()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, SoulKeyRecovery>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianRecoveryAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"recovery", recovery.soul_key.as_ref()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, SoulKeyRecovery>,
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"soul_key", old_soul_key.owner.as_ref()],
+        bump
+    )]
+    pub old_soul_key: Account<'info, SoulKey>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [b"recovery", old_soul_key.key().as_ref()],
+        bump = old_recovery.bump
+    )]
+    pub old_recovery: Account<'info, SoulKeyRecovery>,
+    /// CHECK: Only used as a PDA seed; must be the owner approved by the guardians
+    #[account(
+        constraint = old_recovery.pending_owner == Some(new_owner.key()) @ CProtocolError::InvalidRecoveryOwner
+    )]
+    pub new_owner: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = SoulKey::space_for(old_soul_key.evolution_capacity),
+        seeds = [b"soul_key", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_soul_key: Account<'info, SoulKey>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SoulKeyRecovery::INIT_SPACE,
+        seeds = [b"recovery", new_soul_key.key().as_ref()],
+        bump
+    )]
+    pub new_recovery: Account<'info, SoulKeyRecovery>,
+    /// CHECK: The old owner's Worker, if any; decoded in either layout
+    #[account(mut, address = old_soul_key.owner)]
+    pub old_worker: UncheckedAccount<'info>,
+    // Required when the old owner has a Worker
+    #[account(mut, constraint = new_worker.key() == new_owner.key())]
+    pub new_worker: Option<Account<'info, Worker>>,
+    #[account(mut)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    /// CHECK: The old SoulKey's witness registration; may not exist
+    #[account(
+        mut,
+        seeds = [WITNESS_REGISTRATION_SEED, protocol_state.key().as_ref(), old_soul_key.key().as_ref()],
+        bump
+    )]
+    pub old_witness_registration: UncheckedAccount<'info>,
+    /// CHECK: Soulbound mint of the old SoulKey; may not exist
+    #[account(
+        mut,
+        seeds = [b"soul_mint", old_soul_key.key().as_ref()],
+        bump
+    )]
+    pub old_soul_mint: UncheckedAccount<'info>,
+    /// CHECK: The old owner's associated token account for the soulbound mint; may not exist
+    #[account(
+        mut,
+        address = associated_token::get_associated_token_address_with_program_id(
+            &old_soul_key.owner,
+            &old_soul_mint.key(),
+            &token_program.key()
+        )
+    )]
+    pub old_soul_token_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_capacity: u16)]
+pub struct ExpandSoulKeyHistory<'info> {
+    #[account(
+        mut,
+        seeds = [b"soul_key", owner.key().as_ref()],
+        bump,
+        has_one = owner,
+        realloc = SoulKey::space_for(new_capacity.clamp(INITIAL_EVOLUTION_CAPACITY, MAX_EVOLUTION_CAPACITY)),
+        realloc::payer = owner,
+        realloc::zero = false
+    )]
+    pub soul_key: Account<'info, SoulKey>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintSoulKeyNft<'info> {
+    #[account(
+        mut,
+        seeds = [b"soul_key", owner.key().as_ref()],
+        bump
+    )]
+    pub soul_key: Account<'info, SoulKey>,
+    /// CHECK: Created and initialized as a Token-2022 mint in the instruction
+    #[account(
+        mut,
+        seeds = [b"soul_mint", soul_key.key().as_ref()],
+        bump
+    )]
+    pub soul_mint: UncheckedAccount<'info>,
+    /// CHECK: Associated token account created by the associated token program
+    #[account(mut)]
+    pub owner_token_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SyncSoulKeyMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [b"soul_key", soul_key.owner.as_ref()],
+        bump
+    )]
+    pub soul_key: Account<'info, SoulKey>,
+    /// CHECK: Soulbound mint derived from the SoulKey; validated by the token program
+    #[account(
+        mut,
+        seeds = [b"soul_mint", soul_key.key().as_ref()],
+        bump
+    )]
+    pub soul_mint: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct RecordCollaborativeWork<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = CollaborativeWork::SPACE,
+        seeds = [
+            b"collab",
+            authority.key().as_ref(),
+            &worker.total_work_completed.to_le_bytes()
+        ],
+        bump
+    )]
+    pub collaborative_work: Account<'info, CollaborativeWork>,
+    // Read-only: the initiator is credited through remaining accounts like every other contributor
+    #[account(constraint = worker.key() == authority.key())]
+    pub worker: Account<'info, Worker>,
+    #[account(mut)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    // Required once reward epochs are live; see accrue_effort
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &protocol_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Option<Account<'info, RewardEpoch>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptCollaborativeWork<'info> {
+    #[account(mut)]
+    pub collaborative_work: Account<'info, CollaborativeWork>,
+    #[account(
+        mut,
+        constraint = worker.key() == authority.key()
+    )]
+    pub worker: Account<'info, Worker>,
+    #[account(
+        mut,
+        seeds = [b"soul_key", authority.key().as_ref()],
+        bump
+    )]
+    pub soul_key: Account<'info, SoulKey>,
+    #[account(mut)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    // Required once reward epochs are live; see accrue_effort
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &protocol_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Option<Account<'info, RewardEpoch>>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(bounty_id: u64)]
+pub struct CreateBounty<'info> {
+    #[account(
+        init,
+        payer = sponsor,
+        space = Bounty::SPACE,
+        seeds = [b"bounty", sponsor.key().as_ref(), &bounty_id.to_le_bytes()],
+        bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+    #[account(
+        init,
+        payer = sponsor,
+        token::mint = reward_mint,
+        token::authority = bounty,
+        seeds = [b"bounty_escrow", bounty.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    pub reward_mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    #[account(mut)]
+    pub sponsor_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBounty<'info> {
+    #[account(mut)]
+    pub bounty: Account<'info, Bounty>,
+    #[account(constraint = worker.key() == authority.key())]
+    pub worker: Account<'info, Worker>,
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveBounty<'info> {
+    #[account(mut)]
+    pub bounty: Account<'info, Bounty>,
+    #[account(
+        mut,
+        seeds = [b"bounty_escrow", bounty.key().as_ref()],
+        bump = bounty.escrow_bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = Some(claimant_worker.key()) == bounty.claimant @ CProtocolError::BountyClaimantMismatch
+    )]
+    pub claimant_worker: Account<'info, Worker>,
+    #[account(
+        mut,
+        seeds = [b"soul_key", claimant_worker.key().as_ref()],
+        bump
+    )]
+    pub claimant_soul_key: Account<'info, SoulKey>,
+    #[account(
+        mut,
+        constraint = claimant_token_account.owner == claimant_worker.key() @ CProtocolError::BountyClaimantMismatch,
+        constraint = claimant_token_account.mint == bounty.reward_mint
+    )]
+    pub claimant_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Receives the escrow's rent once it is paid out
+    #[account(mut, address = bounty.sponsor)]
+    pub sponsor: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    // Required once reward epochs are live; see accrue_effort
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &protocol_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Option<Account<'info, RewardEpoch>>,
+    pub reviewer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelBounty<'info> {
+    #[account(
+        mut,
+        has_one = sponsor,
+        close = sponsor
+    )]
+    pub bounty: Account<'info, Bounty>,
+    #[account(
+        mut,
+        seeds = [b"bounty_escrow", bounty.key().as_ref()],
+        bump = bounty.escrow_bump
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    #[account(
+        mut,
+        constraint = sponsor_token_account.mint == bounty.reward_mint
+    )]
+    pub sponsor_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(organization_id: u64)]
+pub struct CreateOrganization<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = Organization::SPACE,
+        seeds = [b"organization", creator.key().as_ref(), &organization_id.to_le_bytes()],
+        bump
+    )]
+    pub organization: Account<'info, Organization>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(has_one = authority @ CProtocolError::UnauthorizedAuthority)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageOrganization<'info> {
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOrganizationBudget<'info> {
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+    #[account(has_one = authority @ CProtocolError::UnauthorizedAuthority)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordOrganizationWork<'info> {
+    #[account(mut)]
+    pub organization: Account<'info, Organization>,
+    #[account(
+        mut,
+        constraint = worker.key() == authority.key()
+    )]
+    pub worker: Account<'info, Worker>,
+    #[account(mut)]
+    pub protocol_state: Account<'info, ProtocolState>,
+    // Both required once reward epochs are live; see accrue_effort. A passed
+    // SoulKey records the work either way.
+    #[account(
+        mut,
+        seeds = [b"soul_key", authority.key().as_ref()],
+        bump
+    )]
+    pub soul_key: Option<Account<'info, SoulKey>>,
+    #[account(
+        mut,
+        seeds = [b"reward_epoch", protocol_state.key().as_ref(), &protocol_state.current_epoch.to_le_bytes()],
+        bump = reward_epoch.bump
+    )]
+    pub reward_epoch: Option<Account<'info, RewardEpoch>>,
+    pub authority: Signer<'info>,
+    pub verifier: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyDecay<'info> {
+    #[account(mut)]
+    pub worker: Account<'info, Worker>,
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+#[derive(Accounts)]
+pub struct MintWithConsensus<'info> {
+    #[account(mut)]
+    pub worker: Account<'info, Worker>,
+    // Liquid mints can only land in the worker's own C token account
+    #[account(
+        mut,
+        token::mint = c_token_mint,
+        token::authority = worker
+    )]
+    pub worker_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = protocol_state.c_token_mint
+    )]
+    pub c_token_mint: Account<'info, Mint>,
+    pub protocol_state: Account<'info, ProtocolState>,
+    /// CHECK: Mint authority PDA; only signs
+    #[account(
+        seeds = [PROTOCOL_AUTHORITY_SEED, protocol_state.key().as_ref()],
+        bump
+    )]
+    pub protocol_authority: UncheckedAccount<'info>,
+    // Required exactly when the work record names the organization that vouched for it
+    pub organization: Option<Account<'info, Organization>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VestingEscrow::INIT_SPACE,
+        seeds = [b"vesting", worker.key().as_ref(), &worker.vesting_count.to_le_bytes()],
+        bump
+    )]
+    pub vesting_escrow: Option<Account<'info, VestingEscrow>>,
+    #[account(
+        init,
+        payer = payer,
+        token::mint = c_token_mint,
+        token::authority = protocol_authority,
+        seeds = [b"vesting_vault", worker.key().as_ref(), &worker.vesting_count.to_le_bytes()],
+        bump
+    )]
+    pub vesting_vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseVested<'info> {
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        has_one = beneficiary,
+        seeds = [b"vesting", vesting_escrow.worker.as_ref(), &vesting_escrow.vesting_id.to_le_bytes()],
+        bump = vesting_escrow.bump
+    )]
+    pub vesting_escrow: Account<'info, VestingEscrow>,
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", vesting_escrow.worker.as_ref(), &vesting_escrow.vesting_id.to_le_bytes()],
+        bump = vesting_escrow.vault_bump
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = vesting_vault.mint,
+        token::authority = beneficiary
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Vault authority PDA; only signs
+    #[account(
+        seeds = [PROTOCOL_AUTHORITY_SEED, protocol_state.key().as_ref()],
+        bump
+    )]
+    pub protocol_authority: UncheckedAccount<'info>,
+    pub beneficiary: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExitVesting<'info> {
+    pub protocol_state: Account<'info, ProtocolState>,
+    #[account(
+        mut,
+        has_one = beneficiary,
+        close = beneficiary,
+        seeds = [b"vesting", vesting_escrow.worker.as_ref(), &vesting_escrow.vesting_id.to_le_bytes()],
+        bump = vesting_escrow.bump
+    )]
+    pub vesting_escrow: Account<'info, VestingEscrow>,
+    #[account(
+        mut,
+        seeds = [b"vesting_vault", vesting_escrow.worker.as_ref(), &vesting_escrow.vesting_id.to_le_bytes()],
+        bump = vesting_escrow.vault_bump
+    )]
+    pub vesting_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = vesting_vault.mint,
+        token::authority = beneficiary
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = protocol_state.decay_destination @ CProtocolError::InvalidDecayDestination
+    )]
+    pub decay_destination: Account<'info, TokenAccount>,
+    /// CHECK: Vault authority PDA; only signs
+    #[account(
+        seeds = [PROTOCOL_AUTHORITY_SEED, protocol_state.key().as_ref()],
+        bump
+    )]
+    pub protocol_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct StakeToEmit<'info> {
+    #[account(mut)]
+    pub worker: Account<'info, Worker>,
+    #[account(mut)]
+    pub worker_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub protocol_vault: Account<'info, TokenAccount>,
+    pub worker_authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateWorker<'info> {
+    /// CHECK: Decoded by hand since it may not deserialize as the current Worker
+    #[account(
+        mut,
+        owner = crate::ID,
+        constraint = worker.key() == authority.key()
+    )]
+    pub worker: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateProtocolState<'info> {
+    /// CHECK: Decoded by hand since it may not deserialize as the current ProtocolState
+    #[account(mut, owner = crate::ID)]
+    pub protocol_state: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::CProtocol>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ CProtocolError::UnauthorizedAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+// Events
+#[event]
+pub struct ProtocolConfigUpdated {
+    pub authority: Pubkey,
+    pub streak_break_days: u16,
+}
+
+#[event]
+pub struct AttestorUpdated {
+    pub authority: Pubkey,
+    pub attestor: Pubkey,
+}
+
+#[event]
+pub struct EpochEmissionUpdated {
+    pub authority: Pubkey,
+    pub epoch_emission: u64,
+}
+
+#[event]
+pub struct DecayDestinationUpdated {
+    pub authority: Pubkey,
+    pub decay_destination: Pubkey,
+}
+
+#[event]
+pub struct VestingScheduleUpdated {
+    pub organization: Option<Pubkey>, // None for the protocol-wide schedule
+    pub work_type: WorkType,
+    pub schedule: VestingSchedule,
+}
+
+#[event]
+pub struct RewardEpochOpened {
+    pub epoch: u64,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub emission_budget: u64,
+}
+
+#[event]
+pub struct RewardEpochClosed {
+    pub epoch: u64,
+    pub total_effort_points: u64,
+}
+
+#[event]
+pub struct EpochRewardsRootPosted {
+    pub epoch: u64,
+    pub merkle_root: [u8; 32],
+}
+
+#[event]
+pub struct EpochRewardsClaimed {
+    pub epoch: u64,
+    pub claimant: Pubkey,
+    pub effort_points: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DistributionProposed {
+    pub distribution: Pubkey,
+    pub epoch: u64,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub leaf_count: u32,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct DistributionRejected {
+    pub distribution: Pubkey,
+    pub epoch: u64,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct WitnessWeightUpdated {
+    pub soul_key: Pubkey,
+    pub weight: u64,
+    pub total_witness_trust: u64,
+}
+
+#[event]
+pub struct DistributionApproved {
+    pub distribution: Pubkey,
+    pub witness: Pubkey,
+    pub weight: u64,
+    pub approval_weight: u64,
+    pub approved: bool,
+}
+
+#[event]
+pub struct DistributionClaimed {
+    pub distribution: Pubkey,
+    pub worker: Pubkey,
+    pub index: u32,
+    pub amount: u64,
+    pub claimed_count: u32,
+}
+
+#[event]
+pub struct WorkRecorded {
+    pub worker: Pubkey,
+    pub work_type: WorkType,
+    pub effort_weight: u64,
+    pub emission_amount: u64,
+    pub effort_points: u64,
+    pub reward_epoch: u64,
+    pub timestamp: i64,
+    pub requires_witness: bool,
+}
 
-        worker.last_decay_check = clock.unix_timestamp;
-        Ok(())
-    }
+#[event]
+pub struct WitnessVoteSettled {
+    pub vote: Pubkey,
+    pub witness: Pubkey,
+    pub aligned: bool,
+    pub slashed: bool,
+    pub trust_score: u64,
+    pub accuracy_bps: u64,
+}
 
-    pub fn mint_with_consensus(
-        ctx: Context<MintWithConsensus>,
-        amount: u64,
-        witness_signatures: Vec<WitnessSignature>,
-    ) -> Result<()> {
-        let protocol_state = &ctx.accounts.protocol_state;
-        
-        // Verify witness consensus
-        let total_weight = witness_signatures.iter().map(|w| w.weight).sum::<u64>();
-        require!(
-            total_weight >= protocol_state.witness_threshold,
-            CProtocolError::InsufficientConsensus
-        );
+#[event]
+pub struct SoulKeyAttested {
+    pub soul_key: Pubkey,
+    pub owner: Pubkey,
+    pub slot: u64,
+}
 
-        // Verify each signature
-        for signature in &witness_signatures {
-            require!(
-                verify_witness_signature(signature, amount, ctx.accounts.worker.key()),
-                CProtocolError::InvalidWitnessSignature
-            );
-        }
+#[event]
+pub struct LiquidityDepositsRecorded {
+    pub soul_key: Pubkey,
+    pub owner: Pubkey,
+    pub total_deposits: u32,
+    pub badge_mask: u16,
+}
 
-        // Mint tokens to worker
-        let cpi_accounts = MintTo {
-            mint: ctx.accounts.c_token_mint.to_account_info(),
-            to: ctx.accounts.worker_token_account.to_account_info(),
-            authority: ctx.accounts.protocol_authority.to_account_info(),
-        };
-        
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::mint_to(cpi_ctx, amount)?;
+#[event]
+pub struct RecoveryGuardiansUpdated {
+    pub soul_key: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+}
 
-        // Update worker pending tokens
-        let worker = &mut ctx.accounts.worker;
-        worker.pending_tokens = worker.pending_tokens.saturating_sub(amount);
-        worker.total_tokens_minted += amount;
+#[event]
+pub struct RecoveryInitiated {
+    pub soul_key: Pubkey,
+    pub guardian: Pubkey,
+    pub new_owner: Pubkey,
+    pub executable_at: i64,
+}
 
-        emit!(TokensMinted {
-            worker: worker.key(),
-            amount,
-            witness_count: witness_signatures.len() as u8,
-            consensus_weight: total_weight,
-        });
+#[event]
+pub struct RecoveryApproved {
+    pub soul_key: Pubkey,
+    pub guardian: Pubkey,
+    pub approvals: u8,
+    pub threshold: u8,
+}
 
-        Ok(())
-    }
+#[event]
+pub struct RecoveryCancelled {
+    pub soul_key: Pubkey,
+}
 
-    pub fn stake_to_emit(ctx: Context<StakeToEmit>, amount: u64) -> Result<()> {
-        let worker = &mut ctx.accounts.worker;
-        
-        // Transfer stake to protocol
-        let cpi_accounts = token::Transfer {
-            from: ctx.accounts.worker_token_account.to_account_info(),
-            to: ctx.accounts.protocol_vault.to_account_info(),
-            authority: ctx.accounts.worker_authority.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+#[event]
+pub struct RecoveryVetoed {
+    pub soul_key: Pubkey,
+    pub executable_at: i64, // Guardians must re-approve before this to override
+}
 
-        worker.staked_amount += amount;
-        worker.stake_timestamp = Clock::get()?.unix_timestamp;
+#[event]
+pub struct RecoveryGuardiansQueued {
+    pub soul_key: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub effective_at: i64,
+}
 
-        emit!(WorkerStaked {
-            worker: worker.key(),
-            amount,
-            total_staked: worker.staked_amount,
-        });
+#[event]
+pub struct OwnerRotated {
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub old_soul_key: Pubkey,
+    pub new_soul_key: Pubkey,
+    pub rotations: u16,
+    pub worker_migrated: bool,
+    pub nft_burned: bool, // False if the old NFT could only have its metadata cleared
+    pub vesting_escrows_moved: u32,
+    pub witness_deregistered: bool,
+}
 
-        Ok(())
-    }
+#[event]
+pub struct SoulKeyHistoryExpanded {
+    pub soul_key: Pubkey,
+    pub old_capacity: u16,
+    pub new_capacity: u16,
+    pub account_size: u32,
 }
 
-// Data Structures
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
-pub enum WorkType {
-    OnboardUser,
-    CreateContent,
-    WriteCode,
-    ReferClient,
-    CloseDeal,
-    CommunityManagement,
-    BugReport,
-    Documentation,
-    Marketing,
-    UserSupport,
+#[event]
+pub struct SoulKeyNftMinted {
+    pub soul_key: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct WorkRecord {
+#[event]
+pub struct SoulKeyMetadataSynced {
+    pub soul_key: Pubkey,
+    pub mint: Pubkey,
+    pub evolution_level: u8,
+    pub badge_count: u8,
+}
+
+#[event]
+pub struct CollaborativeWorkRecorded {
+    pub collaboration: Pubkey,
+    pub initiator: Pubkey,
     pub work_type: WorkType,
     pub effort_weight: u64,
+    pub total_emission: u64,
+    pub contributor_count: u8,
     pub timestamp: i64,
+}
+
+#[event]
+pub struct CollaboratorCredited {
+    pub collaboration: Pubkey,
+    pub worker: Pubkey,
+    pub share_bps: u16,
     pub emission_amount: u64,
-    pub metadata_hash: u64,
+    pub effort_points: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct WitnessSignature {
-    pub witness_pubkey: Pubkey,
-    pub weight: u64, // Voting power
-    pub signature: [u8; 64],
-    pub timestamp: i64,
+#[event]
+pub struct BountyCreated {
+    pub bounty: Pubkey,
+    pub sponsor: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_amount: u64,
+    pub work_type: WorkType,
 }
 
-// Account Structures
-#[account]
-pub struct ProtocolState {
-    pub total_work_recorded: u64,
-    pub total_tokens_emitted: u64,
-    pub decay_rate: u16, // Basis points per epoch
-    pub witness_threshold: u64, // Minimum consensus weight
-    pub min_stake_to_emit: u64,
+#[event]
+pub struct BountyClaimed {
+    pub bounty: Pubkey,
+    pub claimant: Pubkey,
+    pub claim_deadline: i64,
 }
 
-#[account]
-pub struct Worker {
-    pub total_work_completed: u64,
-    pub last_work_timestamp: i64,
-    pub last_activity_timestamp: i64,
-    pub last_decay_check: i64,
-    pub pending_tokens: u64,
-    pub total_tokens_minted: u64,
-    pub total_tokens_decayed: u64,
-    pub staked_amount: u64,
-    pub stake_timestamp: i64,
-    pub work_diversity_score: u64,
-    pub work_history: Vec<WorkRecord>,
-    pub recent_tasks: Vec<u64>, // Task hashes for anti-gaming
+#[event]
+pub struct BountyApproved {
+    pub bounty: Pubkey,
+    pub reviewer: Pubkey,
+    pub approvals: u8,
+    pub quorum_reached: bool,
 }
 
-// Context Structures
-#[derive(Accounts)]
-pub struct InitializeProtocol<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + 64
-    )]
-    pub protocol_state: Account<'info, ProtocolState>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct BountyPaid {
+    pub bounty: Pubkey,
+    pub claimant: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_amount: u64,
+    pub emission_amount: u64,
+    pub effort_points: u64,
 }
 
-#[derive(Accounts)]
-pub struct RecordWork<'info> {
-    #[account(
-        mut,
-        constraint = worker.key() == authority.key()
-    )]
-    pub worker: Account<'info, Worker>,
-    #[account(mut)]
-    pub protocol_state: Account<'info, ProtocolState>,
-    pub authority: Signer<'info>,
+#[event]
+pub struct BountyCancelled {
+    pub bounty: Pubkey,
+    pub sponsor: Pubkey,
+    pub refunded_amount: u64,
 }
 
-#[derive(Accounts)]
-pub struct ApplyDecay<'info> {
-    #[account(mut)]
-    pub worker: Account<'info, Worker>,
-    pub protocol_state: Account<'info, ProtocolState>,
+#[event]
+pub struct OrganizationCreated {
+    pub organization: Pubkey,
+    pub creator: Pubkey,
+    pub emission_budget: u64,
 }
 
-#[derive(Accounts)]
-pub struct MintWithConsensus<'info> {
-    #[account(mut)]
-    pub worker: Account<'info, Worker>,
-    #[account(mut)]
-    pub worker_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub c_token_mint: Account<'info, Mint>,
-    pub protocol_state: Account<'info, ProtocolState>,
-    /// CHECK: Protocol authority PDA
-    pub protocol_authority: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct OrganizationMemberUpdated {
+    pub organization: Pubkey,
+    pub member: Pubkey,
+    pub added: bool,
 }
 
-#[derive(Accounts)]
-pub struct StakeToEmit<'info> {
-    #[account(mut)]
-    pub worker: Account<'info, Worker>,
-    #[account(mut)]
-    pub worker_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub protocol_vault: Account<'info, TokenAccount>,
-    pub worker_authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct WorkTypeConfigUpdated {
+    pub organization: Pubkey,
+    pub work_type: WorkType,
+    pub base_emission: u64,
+    pub cooldown: i64,
 }
 
-// Events
 #[event]
-pub struct WorkRecorded {
+pub struct OrganizationBudgetUpdated {
+    pub organization: Pubkey,
+    pub emission_budget: u64,
+    pub emission_used: u64,
+}
+
+#[event]
+pub struct OrganizationWorkRecorded {
+    pub organization: Pubkey,
     pub worker: Pubkey,
+    pub verifier: Pubkey,
     pub work_type: WorkType,
     pub effort_weight: u64,
     pub emission_amount: u64,
-    pub timestamp: i64,
-    pub requires_witness: bool,
+    pub effort_points: u64,
+    pub budget_remaining: u64,
 }
 
 #[event]
@@ -340,6 +3249,31 @@ pub struct TokensMinted {
     pub consensus_weight: u64,
 }
 
+#[event]
+pub struct TokensVesting {
+    pub worker: Pubkey,
+    pub vesting_escrow: Pubkey,
+    pub amount: u64,
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+}
+
+#[event]
+pub struct VestedTokensReleased {
+    pub vesting_escrow: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub released_amount: u64,
+}
+
+#[event]
+pub struct VestingExited {
+    pub vesting_escrow: Pubkey,
+    pub beneficiary: Pubkey,
+    pub released_amount: u64,
+    pub penalty: u64,
+}
+
 #[event]
 pub struct WorkerStaked {
     pub worker: Pubkey,
@@ -347,8 +3281,22 @@ pub struct WorkerStaked {
     pub total_staked: u64,
 }
 
+#[event]
+pub struct WorkerMigrated {
+    pub worker: Pubkey,
+    pub work_records: u32,
+    pub pending_tokens: u64,
+}
+
+#[event]
+pub struct ProtocolStateMigrated {
+    pub protocol_state: Pubkey,
+    pub authority: Pubkey,
+    pub total_work_recorded: u64,
+}
+
 // Helper Functions
-fn get_base_emission(work_type: &WorkType) -> u64 {
+pub(crate) fn get_base_emission(work_type: &WorkType) -> u64 {
     match work_type {
         WorkType::OnboardUser => 500_000, // 0.0005 tokens
         WorkType::CreateContent => 1_000_000, // 0.001 tokens
@@ -363,7 +3311,78 @@ fn get_base_emission(work_type: &WorkType) -> u64 {
     }
 }
 
-fn get_cooldown(work_type: &WorkType) -> i64 {
+fn calculate_emission(work_type: &WorkType, effort_weight: u64) -> Result<u64> {
+    let emission = get_base_emission(work_type)
+        .checked_mul(effort_weight)
+        .ok_or(CProtocolError::MathOverflow)?
+        .checked_div(100)
+        .ok_or(CProtocolError::MathOverflow)?;
+    Ok(emission)
+}
+
+fn push_work_record(worker: &mut Worker, mut record: WorkRecord, protocol_state: &ProtocolState) {
+    // Once reward epochs are live, work pays out through effort points instead
+    if protocol_state.reward_epochs_live() {
+        record.unminted_amount = 0;
+    }
+    worker.total_work_completed += 1;
+    worker.last_work_timestamp = record.timestamp;
+    worker.pending_tokens += record.unminted_amount;
+    worker.work_diversity_score = calculate_diversity_score(&worker.work_history, &record.work_type);
+    worker.work_history.push(record);
+}
+
+fn credit_collaborator(
+    worker_key: Pubkey,
+    worker: &mut Worker,
+    soul_key: &mut SoulKey,
+    protocol_state: &mut ProtocolState,
+    reward_epoch: Option<&mut RewardEpoch>,
+    collaboration: &mut CollaborativeWork,
+    clock: &Clock,
+) -> Result<(CollaboratorShare, u64)> {
+    let work_type = collaboration.work_type;
+    let effort_weight = collaboration.effort_weight;
+    let metadata_hash = collaboration.metadata_hash;
+
+    let share = collaboration.contributor_mut(&worker_key)?;
+    require!(!share.accepted, CProtocolError::CollaborationAlreadyAccepted);
+    require!(
+        worker.staked_amount >= protocol_state.min_stake_to_emit,
+        CProtocolError::InsufficientStake
+    );
+    // Each contributor, the initiator included, passes the same duplicate and
+    // cooldown checks as solo work
+    let task_hash = hash_collaboration_task(&work_type, metadata_hash, worker_key);
+    track_task(worker, task_hash, get_cooldown(&work_type), clock)?;
+    share.accepted = true;
+
+    let record = WorkRecord {
+        work_type,
+        effort_weight,
+        timestamp: clock.unix_timestamp,
+        emission_amount: share.emission_amount,
+        metadata_hash,
+        organization: None,
+        unminted_amount: share.emission_amount,
+    };
+
+    let effort_points = accrue_effort(
+        protocol_state,
+        reward_epoch,
+        Some(soul_key),
+        share.emission_amount,
+        clock,
+    )?;
+    soul_key.update_after_work(&record, protocol_state.streak_break_days, clock)?;
+    push_work_record(worker, record, protocol_state);
+    soul_key.add_pool_participation();
+
+    protocol_state.total_tokens_emitted += share.emission_amount;
+    Ok((share.clone(), effort_points))
+}
+
+pub(crate) fn get_cooldown(work_type: &WorkType) -> i64 {
     match work_type {
         WorkType::OnboardUser => 3600, // 1 hour
         WorkType::CreateContent => 7200, // 2 hours
@@ -384,6 +3403,140 @@ fn hash_task(work_type: &WorkType, metadata: &str, worker: Pubkey) -> u64 {
     hasher.finish()
 }
 
+fn hash_collaboration_task(work_type: &WorkType, metadata_hash: u64, worker: Pubkey) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    use std::hash::{Hash, Hasher};
+    work_type.hash(&mut hasher);
+    metadata_hash.hash(&mut hasher);
+    worker.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Anti-gaming shared by every path that credits work: a task may not repeat
+// within its cooldown, and only the last few task hashes are remembered
+fn track_task(worker: &mut Worker, task_hash: u64, cooldown: i64, clock: &Clock) -> Result<()> {
+    require!(
+        !worker.recent_tasks.contains(&task_hash) ||
+        clock.unix_timestamp > worker.last_work_timestamp + cooldown,
+        CProtocolError::TaskCooldownActive
+    );
+
+    if worker.recent_tasks.len() >= 10 {
+        worker.recent_tasks.remove(0);
+    }
+    worker.recent_tasks.push(task_hash);
+    Ok(())
+}
+
+/// Moves a recovered owner's Worker into the new owner's unused Worker and
+/// returns what the old account keeps: only its vesting counter, so escrow
+/// seeds under the old key stay unique.
+fn transfer_worker_state(old_worker: Worker, new_worker: &mut Worker) -> Result<Worker> {
+    require!(
+        new_worker.total_work_completed == 0
+            && new_worker.staked_amount == 0
+            && new_worker.pending_tokens == 0
+            && new_worker.work_history.is_empty(),
+        CProtocolError::RecoveryWorkerNotEmpty
+    );
+    let retained = Worker {
+        vesting_count: old_worker.vesting_count,
+        ..Worker::default()
+    };
+    *new_worker = Worker {
+        vesting_count: new_worker.vesting_count,
+        ..old_worker
+    };
+    Ok(retained)
+}
+
+/// Where a reward mint lands: the worker's own token account when `schedule`
+/// is liquid, a fresh vesting vault when it vests. Vesting accounts must be
+/// passed exactly in the second case.
+fn reward_destination<'info>(
+    schedule: &VestingSchedule,
+    liquid: &Account<'info, TokenAccount>,
+    vesting_escrow: &Option<Account<'info, VestingEscrow>>,
+    vesting_vault: &Option<Account<'info, TokenAccount>>,
+) -> Result<AccountInfo<'info>> {
+    match (vesting_escrow, vesting_vault) {
+        (None, None) if !schedule.is_vested() => Ok(liquid.to_account_info()),
+        (Some(_), Some(vault)) if schedule.is_vested() => Ok(vault.to_account_info()),
+        _ => err!(CProtocolError::VestingAccountsMismatch),
+    }
+}
+
+/// Records tokens just minted into a vesting vault and advances the worker's vesting ids.
+fn open_vesting_escrow(
+    vesting_escrow: &mut Account<VestingEscrow>,
+    worker: &mut Account<Worker>,
+    schedule: VestingSchedule,
+    amount: u64,
+    bump: u8,
+    vault_bump: u8,
+) -> Result<()> {
+    vesting_escrow.set_inner(VestingEscrow {
+        worker: worker.key(),
+        beneficiary: worker.key(),
+        vesting_id: worker.vesting_count,
+        schedule,
+        start_timestamp: Clock::get()?.unix_timestamp,
+        total_amount: amount,
+        released_amount: 0,
+        vault_bump,
+        bump,
+    });
+    worker.vesting_count += 1;
+
+    emit!(TokensVesting {
+        worker: worker.key(),
+        vesting_escrow: vesting_escrow.key(),
+        amount,
+        cliff_seconds: schedule.cliff_seconds,
+        duration_seconds: schedule.duration_seconds,
+    });
+    Ok(())
+}
+
+/// Closes a program account that is not deserialized in the instruction context.
+fn close_program_account<'info>(account: &AccountInfo<'info>, destination: &AccountInfo<'info>) -> Result<()> {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? += lamports;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+/// Reallocates a program account up to `len`, topping up rent from `payer`.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if len <= account.data_len() {
+        return Ok(());
+    }
+    let top_up = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    account.realloc(len, false)?;
+    Ok(())
+}
+
 fn hash_string(s: &str) -> u64 {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     use std::hash::{Hash, Hasher};
@@ -418,4 +3571,174 @@ pub enum CProtocolError {
     InsufficientConsensus,
     #[msg("Invalid witness signature")]
     InvalidWitnessSignature,
+    #[msg("Collaborative work needs between 2 and MAX_COLLABORATORS contributors")]
+    InvalidCollaboratorCount,
+    #[msg("Collaborator shares must be non-zero and sum to 10000 basis points")]
+    InvalidCollaboratorSplit,
+    #[msg("Collaborator listed more than once")]
+    DuplicateCollaborator,
+    #[msg("Initiator must be one of the collaborators")]
+    InitiatorNotCollaborator,
+    #[msg("Collaborator accounts do not match the submitted splits")]
+    CollaboratorAccountMismatch,
+    #[msg("Signer is not a collaborator on this work record")]
+    NotACollaborator,
+    #[msg("Collaborator share has already been accepted")]
+    CollaborationAlreadyAccepted,
+    #[msg("SoulKey does not belong to this worker")]
+    SoulKeyOwnerMismatch,
+    #[msg("Bounty reward must be greater than zero")]
+    InvalidBountyReward,
+    #[msg("Bounty description is too long")]
+    BountyDescriptionTooLong,
+    #[msg("Bounty needs between 1 and MAX_BOUNTY_REVIEWERS unique reviewers")]
+    InvalidBountyReviewers,
+    #[msg("Approval threshold must be between 1 and the number of reviewers")]
+    InvalidApprovalThreshold,
+    #[msg("Bounty is not open")]
+    BountyNotOpen,
+    #[msg("Bounty has not been claimed")]
+    BountyNotClaimed,
+    #[msg("Signer is not a reviewer for this bounty")]
+    NotABountyReviewer,
+    #[msg("Reviewer has already approved this bounty")]
+    BountyAlreadyApproved,
+    #[msg("Accounts do not belong to the bounty claimant")]
+    BountyClaimantMismatch,
+    #[msg("Organization name is too long")]
+    OrganizationNameTooLong,
+    #[msg("Organization needs between 1 and MAX_ORGANIZATION_ADMINS unique admins")]
+    InvalidOrganizationAdmins,
+    #[msg("Signer is not an organization admin")]
+    NotAnOrganizationAdmin,
+    #[msg("Organization member roster is full")]
+    OrganizationFull,
+    #[msg("Worker is already an organization member")]
+    AlreadyOrganizationMember,
+    #[msg("Worker is not an organization member")]
+    NotAnOrganizationMember,
+    #[msg("Work type config is invalid")]
+    InvalidWorkTypeConfig,
+    #[msg("Organization emission budget exceeded")]
+    OrganizationBudgetExceeded,
+    #[msg("SoulKey metadata URI is too long")]
+    SoulNftUriTooLong,
+    #[msg("Signer is not the protocol authority")]
+    UnauthorizedAuthority,
+    #[msg("Streak break threshold must be at least one day")]
+    InvalidStreakBreakDays,
+    #[msg("Evolution capacity must grow and stay within MAX_EVOLUTION_CAPACITY")]
+    InvalidEvolutionCapacity,
+    #[msg("Guardians must be unique, exclude the owner and number at most MAX_GUARDIANS")]
+    InvalidGuardians,
+    #[msg("Recovery threshold must be between 1 and the number of guardians")]
+    InvalidRecoveryThreshold,
+    #[msg("Signer is not a recovery guardian")]
+    NotAGuardian,
+    #[msg("A recovery is already pending")]
+    RecoveryAlreadyPending,
+    #[msg("No recovery is pending")]
+    NoPendingRecovery,
+    #[msg("Guardian has already approved this recovery")]
+    RecoveryAlreadyApproved,
+    #[msg("Not enough guardian approvals to recover")]
+    InsufficientGuardianApprovals,
+    #[msg("Recovery timelock has not elapsed")]
+    RecoveryTimelockActive,
+    #[msg("New owner does not match the pending recovery")]
+    InvalidRecoveryOwner,
+    #[msg("Reward epochs have already started")]
+    RewardEpochsAlreadyStarted,
+    #[msg("Reward epoch duration must be positive")]
+    InvalidEpochConfig,
+    #[msg("Reward epoch has ended; close it to open the next one")]
+    RewardEpochEnded,
+    #[msg("Reward epoch is still open")]
+    RewardEpochStillOpen,
+    #[msg("Reward root has already been posted for this epoch")]
+    RewardRootAlreadyPosted,
+    #[msg("Reward root has not been posted for this epoch")]
+    RewardRootNotPosted,
+    #[msg("Invalid reward proof")]
+    InvalidRewardProof,
+    #[msg("Claims would exceed the epoch emission budget")]
+    RewardBudgetExceeded,
+    #[msg("Distribution needs between 1 and MAX_DISTRIBUTION_LEAVES leaves and a non-zero total")]
+    InvalidDistribution,
+    #[msg("Witness is not eligible to approve high-value payouts")]
+    WitnessNotEligible,
+    #[msg("Witness has already approved this distribution")]
+    DistributionAlreadyApproved,
+    #[msg("Distribution already has MAX_DISTRIBUTION_WITNESSES approvals")]
+    DistributionWitnessesFull,
+    #[msg("Distribution has already reached consensus")]
+    DistributionAlreadyFinalized,
+    #[msg("Distribution has not reached witness consensus")]
+    DistributionNotApproved,
+    #[msg("Invalid distribution proof")]
+    InvalidDistributionProof,
+    #[msg("Distribution leaf has already been claimed")]
+    DistributionAlreadyClaimed,
+    #[msg("Claims would exceed the distribution total")]
+    DistributionExhausted,
+    #[msg("Vesting cliff must be within the duration and the penalty at most 10000 basis points")]
+    InvalidVestingSchedule,
+    #[msg("Vesting accounts must be passed exactly when the reward vests")]
+    VestingAccountsMismatch,
+    #[msg("No vested tokens to release")]
+    NothingToRelease,
+    #[msg("Account is not the protocol decay destination")]
+    InvalidDecayDestination,
+    #[msg("Witness vote has already been settled")]
+    WitnessVoteAlreadySettled,
+    #[msg("No work record at that index")]
+    InvalidWorkRecord,
+    #[msg("Work record has nothing left to mint")]
+    WorkRecordAlreadyMinted,
+    #[msg("Organization does not match the work record")]
+    OrganizationMismatch,
+    #[msg("Reward epoch and SoulKey accounts are required once reward epochs are live")]
+    RewardEpochAccountsRequired,
+    #[msg("Bounty effort weight is out of range")]
+    InvalidEffortWeight,
+    #[msg("Bounty claim has lapsed")]
+    BountyClaimLapsed,
+    #[msg("Bounty is claimed and its claim has not lapsed")]
+    BountyNotCancellable,
+    #[msg("Account is not a Worker in a known layout")]
+    InvalidWorkerLayout,
+    #[msg("Worker already uses the current layout")]
+    WorkerAlreadyMigrated,
+    #[msg("No guardian change is queued")]
+    NoPendingGuardianUpdate,
+    #[msg("Guardian change is still timelocked")]
+    GuardianUpdateTimelockActive,
+    #[msg("Guardians re-approved the recovery after the owner's veto")]
+    RecoveryVetoOverridden,
+    #[msg("The new owner's Worker is required to move the old owner's Worker")]
+    RecoveryWorkerRequired,
+    #[msg("The new owner's Worker already holds work or stake")]
+    RecoveryWorkerNotEmpty,
+    #[msg("No witness is registered, so consensus cannot be reached")]
+    NoRegisteredWitnesses,
+    #[msg("The voted-on distribution is still open")]
+    WitnessVoteUnresolved,
+    #[msg("Witness vote subject is not a distribution")]
+    InvalidWitnessVoteSubject,
+    #[msg("A bounty's claimant cannot approve it")]
+    BountySelfApproval,
+    #[msg("Too few reviewers besides the claimant to approve this bounty")]
+    BountyApprovalUnreachable,
+    #[msg("Only the protocol attestor can report this")]
+    UnauthorizedAttestor,
+    #[msg("Liquidity deposit total cannot decrease")]
+    LiquidityDepositsDecreased,
+    #[msg("Distribution exceeds the epoch's emission budget")]
+    DistributionExceedsEpochBudget,
+    #[msg("The proposer cannot approve their own distribution")]
+    ProposerCannotApprove,
+    #[msg("Account is not a ProtocolState in a known layout")]
+    InvalidProtocolStateLayout,
+    #[msg("ProtocolState already uses the current layout")]
+    ProtocolStateAlreadyMigrated,
 }
\ No newline at end of file
@@ -0,0 +1,21 @@
+use anchor_lang::solana_program::hash::hashv;
+
+// Domain prefixes keep a leaf from ever being accepted as an inner node
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn merkle_leaf(parts: &[&[u8]]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(parts.len() + 1);
+    data.push(LEAF_PREFIX);
+    data.extend_from_slice(parts);
+    hashv(&data).to_bytes()
+}
+
+/// Verifies a proof built with sorted sibling pairs, so no path bits are needed.
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[NODE_PREFIX, &left, &right]).to_bytes()
+    });
+    computed == *root
+}
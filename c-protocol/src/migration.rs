@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::{CProtocolError, ProtocolState, VestingSchedule, WorkRecord, WorkType, Worker, WORK_TYPE_COUNT};
+
+/// WorkRecord as written before organizations and per-record minting.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyWorkRecord {
+    pub work_type: WorkType,
+    pub effort_weight: u64,
+    pub timestamp: i64,
+    pub emission_amount: u64,
+    pub metadata_hash: u64,
+}
+
+/// Worker as written before WorkRecord gained `organization` and
+/// `unminted_amount` and the account gained `vesting_count`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyWorker {
+    pub total_work_completed: u64,
+    pub last_work_timestamp: i64,
+    pub last_activity_timestamp: i64,
+    pub last_decay_check: i64,
+    pub pending_tokens: u64,
+    pub total_tokens_minted: u64,
+    pub total_tokens_decayed: u64,
+    pub staked_amount: u64,
+    pub stake_timestamp: i64,
+    pub work_diversity_score: u64,
+    pub work_history: Vec<LegacyWorkRecord>,
+    pub recent_tasks: Vec<u64>,
+}
+
+impl LegacyWorker {
+    /// Decodes account data, discriminator included, that does not yet
+    /// deserialize as the current Worker layout.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 && data[..8] == Worker::DISCRIMINATOR,
+            CProtocolError::InvalidWorkerLayout
+        );
+        require!(
+            Worker::try_deserialize(&mut &data[..]).is_err(),
+            CProtocolError::WorkerAlreadyMigrated
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(CProtocolError::InvalidWorkerLayout))
+    }
+
+    /// Legacy pending tokens were never tied to a record, so they are spread
+    /// back over the newest records to keep them mintable and bounded by
+    /// the pending balance.
+    pub fn into_worker(self) -> Worker {
+        let mut unassigned = self.pending_tokens;
+        let mut work_history: Vec<WorkRecord> = self
+            .work_history
+            .into_iter()
+            .map(|record| WorkRecord {
+                work_type: record.work_type,
+                effort_weight: record.effort_weight,
+                timestamp: record.timestamp,
+                emission_amount: record.emission_amount,
+                metadata_hash: record.metadata_hash,
+                organization: None,
+                unminted_amount: 0,
+            })
+            .collect();
+        for record in work_history.iter_mut().rev() {
+            if unassigned == 0 {
+                break;
+            }
+            record.unminted_amount = record.emission_amount.min(unassigned);
+            unassigned -= record.unminted_amount;
+        }
+
+        Worker {
+            total_work_completed: self.total_work_completed,
+            last_work_timestamp: self.last_work_timestamp,
+            last_activity_timestamp: self.last_activity_timestamp,
+            last_decay_check: self.last_decay_check,
+            pending_tokens: self.pending_tokens,
+            total_tokens_minted: self.total_tokens_minted,
+            total_tokens_decayed: self.total_tokens_decayed,
+            staked_amount: self.staked_amount,
+            stake_timestamp: self.stake_timestamp,
+            work_diversity_score: self.work_diversity_score,
+            work_history,
+            recent_tasks: self.recent_tasks,
+            vesting_count: 0,
+        }
+    }
+}
+
+/// Decodes a Worker from account data in either the current or the legacy layout.
+pub fn decode_worker(data: &[u8]) -> Result<Worker> {
+    match Worker::try_deserialize(&mut &data[..]) {
+        Ok(worker) => Ok(worker),
+        Err(_) => Ok(LegacyWorker::try_from_account_data(data)?.into_worker()),
+    }
+}
+
+/// ProtocolState as written by the original `initialize_protocol`, before it
+/// gained an authority, the attestor and the reward, vesting and witness
+/// settings.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyProtocolState {
+    pub total_work_recorded: u64,
+    pub total_tokens_emitted: u64,
+    pub decay_rate: u16,
+    pub witness_threshold: u64,
+    pub min_stake_to_emit: u64,
+}
+
+impl LegacyProtocolState {
+    /// Decodes account data, discriminator included, that does not yet
+    /// deserialize as the current ProtocolState layout.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 && data[..8] == ProtocolState::DISCRIMINATOR,
+            CProtocolError::InvalidProtocolStateLayout
+        );
+        require!(
+            ProtocolState::try_deserialize(&mut &data[..]).is_err(),
+            CProtocolError::ProtocolStateAlreadyMigrated
+        );
+        Self::deserialize(&mut &data[8..]).map_err(|_| error!(CProtocolError::InvalidProtocolStateLayout))
+    }
+
+    /// Keeps the counters and fills the new settings with the defaults of
+    /// `initialize_protocol`. The legacy witness_threshold was an absolute
+    /// weight, so the current 60% share replaces it.
+    pub fn into_protocol_state(self, authority: Pubkey) -> ProtocolState {
+        ProtocolState {
+            total_work_recorded: self.total_work_recorded,
+            total_tokens_emitted: self.total_tokens_emitted,
+            decay_rate: self.decay_rate,
+            witness_threshold: 6000,
+            min_stake_to_emit: self.min_stake_to_emit,
+            authority,
+            streak_break_days: 1,
+            attestor: authority,
+            c_token_mint: Pubkey::default(),
+            epoch_emission: 0,
+            epoch_duration: 0,
+            current_epoch: 0,
+            decay_destination: Pubkey::default(),
+            vesting_schedules: [VestingSchedule::default(); WORK_TYPE_COUNT],
+            total_witness_trust: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_protocol_state_migrates_once() {
+        let legacy = LegacyProtocolState {
+            total_work_recorded: 42,
+            total_tokens_emitted: 7_000,
+            decay_rate: 100,
+            witness_threshold: 6000,
+            min_stake_to_emit: 1_000_000,
+        };
+        // The original account was allocated 8 + 64 bytes
+        let mut data = ProtocolState::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        data.resize(8 + 64, 0);
+
+        let authority = Pubkey::new_unique();
+        let state = LegacyProtocolState::try_from_account_data(&data).unwrap().into_protocol_state(authority);
+        assert_eq!((state.total_work_recorded, state.total_tokens_emitted), (42, 7_000));
+        assert_eq!((state.authority, state.attestor), (authority, authority));
+
+        let mut migrated = Vec::new();
+        state.try_serialize(&mut migrated).unwrap();
+        assert!(LegacyProtocolState::try_from_account_data(&migrated).is_err());
+        assert!(LegacyProtocolState::try_from_account_data(&[0; 72]).is_err());
+    }
+}
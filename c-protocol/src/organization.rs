@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+
+use crate::{get_base_emission, get_cooldown, CProtocolError, VestingSchedule, WorkType};
+
+pub const MAX_ORGANIZATION_NAME_LEN: usize = 32;
+pub const MAX_ORGANIZATION_ADMINS: usize = 5;
+pub const MAX_ORGANIZATION_MEMBERS: usize = 64;
+pub const MAX_WORK_TYPE_OVERRIDES: usize = 10; // One per WorkType
+pub const MAX_BASE_EMISSION_MULTIPLIER: u64 = 2; // Overrides may at most double the protocol rate
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct WorkTypeConfig {
+    pub work_type: WorkType,
+    pub base_emission: u64,
+    pub cooldown: i64, // Seconds
+    pub vesting: Option<VestingSchedule>, // Overrides the protocol-wide schedule
+}
+
+#[account]
+pub struct Organization {
+    pub creator: Pubkey,
+    pub organization_id: u64,
+    pub name: String,
+    pub admins: Vec<Pubkey>,
+    pub members: Vec<Pubkey>,
+    pub work_type_configs: Vec<WorkTypeConfig>,
+    pub emission_budget: u64, // Total emissions the organization may vouch for
+    pub emission_used: u64,
+    pub total_work_vouched: u64,
+    pub created_timestamp: i64,
+    pub bump: u8,
+}
+
+impl Organization {
+    pub const SPACE: usize = 8 // discriminator
+        + 32 // creator
+        + 8 // organization_id
+        + 4 + MAX_ORGANIZATION_NAME_LEN // name
+        + 4 + MAX_ORGANIZATION_ADMINS * 32 // admins
+        + 4 + MAX_ORGANIZATION_MEMBERS * 32 // members
+        + 4 + MAX_WORK_TYPE_OVERRIDES * (1 + 8 + 8 + 1 + VestingSchedule::INIT_SPACE) // work_type_configs
+        + 8 // emission_budget
+        + 8 // emission_used
+        + 8 // total_work_vouched
+        + 8 // created_timestamp
+        + 1; // bump
+
+    pub fn validate_profile(name: &str, admins: &[Pubkey]) -> Result<()> {
+        require!(
+            name.len() <= MAX_ORGANIZATION_NAME_LEN,
+            CProtocolError::OrganizationNameTooLong
+        );
+        require!(
+            !admins.is_empty() && admins.len() <= MAX_ORGANIZATION_ADMINS,
+            CProtocolError::InvalidOrganizationAdmins
+        );
+        for (index, admin) in admins.iter().enumerate() {
+            require!(
+                !admins[..index].contains(admin),
+                CProtocolError::InvalidOrganizationAdmins
+            );
+        }
+        Ok(())
+    }
+
+    pub fn require_admin(&self, signer: &Pubkey) -> Result<()> {
+        require!(
+            self.admins.contains(signer),
+            CProtocolError::NotAnOrganizationAdmin
+        );
+        Ok(())
+    }
+
+    pub fn is_member(&self, worker: &Pubkey) -> bool {
+        self.members.contains(worker)
+    }
+
+    pub fn add_member(&mut self, member: Pubkey) -> Result<()> {
+        require!(!self.is_member(&member), CProtocolError::AlreadyOrganizationMember);
+        require!(
+            self.members.len() < MAX_ORGANIZATION_MEMBERS,
+            CProtocolError::OrganizationFull
+        );
+        self.members.push(member);
+        Ok(())
+    }
+
+    pub fn remove_member(&mut self, member: &Pubkey) -> Result<()> {
+        let index = self
+            .members
+            .iter()
+            .position(|m| m == member)
+            .ok_or(CProtocolError::NotAnOrganizationMember)?;
+        self.members.swap_remove(index);
+        Ok(())
+    }
+
+    /// Overrides stay within MAX_BASE_EMISSION_MULTIPLIER of the protocol's base emission.
+    pub fn validate_work_type_config(config: &WorkTypeConfig) -> Result<()> {
+        let max_base_emission = get_base_emission(&config.work_type)
+            .checked_mul(MAX_BASE_EMISSION_MULTIPLIER)
+            .ok_or(CProtocolError::MathOverflow)?;
+        require!(
+            config.base_emission > 0 && config.base_emission <= max_base_emission,
+            CProtocolError::InvalidWorkTypeConfig
+        );
+        require!(config.cooldown >= 0, CProtocolError::InvalidWorkTypeConfig);
+        match config.vesting {
+            Some(schedule) => schedule.validate(),
+            None => Ok(()),
+        }
+    }
+
+    pub fn upsert_work_type_config(&mut self, config: WorkTypeConfig) {
+        match self
+            .work_type_configs
+            .iter_mut()
+            .find(|c| c.work_type == config.work_type)
+        {
+            Some(existing) => *existing = config,
+            // Bounded by the number of WorkType variants
+            None => self.work_type_configs.push(config),
+        }
+    }
+
+    fn work_type_config(&self, work_type: &WorkType) -> Option<&WorkTypeConfig> {
+        self.work_type_configs.iter().find(|c| &c.work_type == work_type)
+    }
+
+    pub fn base_emission(&self, work_type: &WorkType) -> u64 {
+        self.work_type_config(work_type)
+            .map(|c| c.base_emission)
+            .unwrap_or_else(|| get_base_emission(work_type))
+    }
+
+    pub fn cooldown(&self, work_type: &WorkType) -> i64 {
+        self.work_type_config(work_type)
+            .map(|c| c.cooldown)
+            .unwrap_or_else(|| get_cooldown(work_type))
+    }
+
+    pub fn vesting_schedule(&self, work_type: &WorkType) -> Option<VestingSchedule> {
+        self.work_type_config(work_type).and_then(|c| c.vesting)
+    }
+
+    pub fn calculate_emission(&self, work_type: &WorkType, effort_weight: u64) -> Result<u64> {
+        let emission = self
+            .base_emission(work_type)
+            .checked_mul(effort_weight)
+            .ok_or(CProtocolError::MathOverflow)?
+            .checked_div(100)
+            .ok_or(CProtocolError::MathOverflow)?;
+        Ok(emission)
+    }
+
+    pub fn consume_budget(&mut self, emission_amount: u64) -> Result<()> {
+        let emission_used = self
+            .emission_used
+            .checked_add(emission_amount)
+            .ok_or(CProtocolError::MathOverflow)?;
+        require!(
+            emission_used <= self.emission_budget,
+            CProtocolError::OrganizationBudgetExceeded
+        );
+        self.emission_used = emission_used;
+        self.total_work_vouched += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+
+use crate::CProtocolError;
+
+pub const MAX_GUARDIANS: usize = 5;
+pub const RECOVERY_TIMELOCK: i64 = 3 * 86400; // Window for the current owner to cancel
+pub const GUARDIAN_UPDATE_TIMELOCK: i64 = 7 * 86400; // Outlasts a recovery so guardians can act first
+
+/// Social recovery settings for a SoulKey; derived from the SoulKey address so
+/// it moves along with the reputation when the owner rotates.
+#[account]
+#[derive(InitSpace)]
+pub struct SoulKeyRecovery {
+    pub soul_key: Pubkey,
+    #[max_len(MAX_GUARDIANS)]
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub pending_owner: Option<Pubkey>,
+    #[max_len(MAX_GUARDIANS)]
+    pub approvals: Vec<Pubkey>,
+    pub executable_at: i64,
+    pub owner_vetoed: bool, // The owner cancelled once the quorum was reached
+    #[max_len(MAX_GUARDIANS)]
+    pub pending_guardians: Vec<Pubkey>,
+    pub pending_threshold: u8,
+    pub guardians_effective_at: i64, // Zero when no guardian change is queued
+    pub bump: u8,
+}
+
+impl SoulKeyRecovery {
+    pub fn validate_guardians(owner: &Pubkey, guardians: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !guardians.is_empty() && guardians.len() <= MAX_GUARDIANS,
+            CProtocolError::InvalidGuardians
+        );
+        for (index, guardian) in guardians.iter().enumerate() {
+            require!(
+                guardian != owner && !guardians[..index].contains(guardian),
+                CProtocolError::InvalidGuardians
+            );
+        }
+        require!(
+            threshold > 0 && threshold as usize <= guardians.len(),
+            CProtocolError::InvalidRecoveryThreshold
+        );
+        Ok(())
+    }
+
+    /// Installs the initial guardian set; later changes go through queue_guardians.
+    pub fn configure(&mut self, guardians: Vec<Pubkey>, threshold: u8) {
+        self.guardians = guardians;
+        self.threshold = threshold;
+        self.clear_pending();
+        self.clear_pending_guardians();
+    }
+
+    /// Queues a guardian change behind GUARDIAN_UPDATE_TIMELOCK, so a stolen
+    /// owner key cannot swap out the guardians before they recover the SoulKey.
+    pub fn queue_guardians(&mut self, guardians: Vec<Pubkey>, threshold: u8, clock: &Clock) -> Result<()> {
+        require!(self.pending_owner.is_none(), CProtocolError::RecoveryAlreadyPending);
+        self.pending_guardians = guardians;
+        self.pending_threshold = threshold;
+        self.guardians_effective_at = clock.unix_timestamp + GUARDIAN_UPDATE_TIMELOCK;
+        Ok(())
+    }
+
+    pub fn apply_guardians(&mut self, clock: &Clock) -> Result<()> {
+        require!(
+            self.guardians_effective_at > 0,
+            CProtocolError::NoPendingGuardianUpdate
+        );
+        require!(self.pending_owner.is_none(), CProtocolError::RecoveryAlreadyPending);
+        require!(
+            clock.unix_timestamp >= self.guardians_effective_at,
+            CProtocolError::GuardianUpdateTimelockActive
+        );
+        self.guardians = std::mem::take(&mut self.pending_guardians);
+        self.threshold = self.pending_threshold;
+        self.clear_pending_guardians();
+        Ok(())
+    }
+
+    fn clear_pending_guardians(&mut self) {
+        self.pending_guardians.clear();
+        self.pending_threshold = 0;
+        self.guardians_effective_at = 0;
+    }
+
+    pub fn initiate(&mut self, guardian: Pubkey, new_owner: Pubkey, clock: &Clock) -> Result<()> {
+        require!(self.guardians.contains(&guardian), CProtocolError::NotAGuardian);
+        require!(self.pending_owner.is_none(), CProtocolError::RecoveryAlreadyPending);
+        // Guardians carry over to the rotated SoulKey, where the owner may not guard itself
+        require!(
+            !self.guardians.contains(&new_owner),
+            CProtocolError::InvalidRecoveryOwner
+        );
+
+        self.pending_owner = Some(new_owner);
+        self.approvals = vec![guardian];
+        self.executable_at = clock.unix_timestamp + RECOVERY_TIMELOCK;
+        // A queued guardian change may come from the key being recovered from
+        self.clear_pending_guardians();
+        Ok(())
+    }
+
+    pub fn approve(&mut self, guardian: Pubkey) -> Result<()> {
+        require!(self.guardians.contains(&guardian), CProtocolError::NotAGuardian);
+        require!(self.pending_owner.is_some(), CProtocolError::NoPendingRecovery);
+        require!(
+            !self.approvals.contains(&guardian),
+            CProtocolError::RecoveryAlreadyApproved
+        );
+        self.approvals.push(guardian);
+        Ok(())
+    }
+
+    /// Cancels a recovery for the owner and returns true if it was dropped.
+    /// Once the guardians reached their quorum the owner gets a single veto:
+    /// approvals restart with a fresh timelock, and a quorum that approves
+    /// again overrides it. The owner can only drop a vetoed recovery if the
+    /// guardians let that timelock pass without re-approving.
+    pub fn cancel(&mut self, clock: &Clock) -> Result<bool> {
+        require!(self.pending_owner.is_some(), CProtocolError::NoPendingRecovery);
+        let quorum_reached = self.approvals.len() >= self.threshold as usize;
+
+        if !self.owner_vetoed && quorum_reached {
+            self.owner_vetoed = true;
+            self.approvals.clear();
+            self.executable_at = clock.unix_timestamp + RECOVERY_TIMELOCK;
+            return Ok(false);
+        }
+        if self.owner_vetoed {
+            require!(
+                !quorum_reached && clock.unix_timestamp >= self.executable_at,
+                CProtocolError::RecoveryVetoOverridden
+            );
+        }
+        self.clear_pending();
+        Ok(true)
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending_owner = None;
+        self.approvals.clear();
+        self.executable_at = 0;
+        self.owner_vetoed = false;
+    }
+
+    /// The new owner, once the quorum is reached and the timelock has passed.
+    pub fn ready_owner(&self, clock: &Clock) -> Result<Pubkey> {
+        let new_owner = self.pending_owner.ok_or(CProtocolError::NoPendingRecovery)?;
+        require!(
+            self.approvals.len() >= self.threshold as usize,
+            CProtocolError::InsufficientGuardianApprovals
+        );
+        require!(
+            clock.unix_timestamp >= self.executable_at,
+            CProtocolError::RecoveryTimelockActive
+        );
+        Ok(new_owner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock_at(unix_timestamp: i64) -> Clock {
+        Clock { unix_timestamp, ..Clock::default() }
+    }
+
+    fn recovery(guardians: &[Pubkey], threshold: u8) -> SoulKeyRecovery {
+        let mut recovery = SoulKeyRecovery {
+            soul_key: Pubkey::new_unique(),
+            guardians: Vec::new(),
+            threshold: 0,
+            pending_owner: None,
+            approvals: Vec::new(),
+            executable_at: 0,
+            owner_vetoed: false,
+            pending_guardians: Vec::new(),
+            pending_threshold: 0,
+            guardians_effective_at: 0,
+            bump: 255,
+        };
+        recovery.configure(guardians.to_vec(), threshold);
+        recovery
+    }
+
+    #[test]
+    fn recovery_waits_for_quorum_and_timelock() {
+        let guardians = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let new_owner = Pubkey::new_unique();
+        let mut recovery = recovery(&guardians, 2);
+
+        recovery.initiate(guardians[0], new_owner, &clock_at(1_000)).unwrap();
+        let ready_at = 1_000 + RECOVERY_TIMELOCK;
+        assert!(recovery.ready_owner(&clock_at(ready_at)).is_err());
+
+        recovery.approve(guardians[1]).unwrap();
+        assert!(recovery.approve(guardians[1]).is_err());
+        assert!(recovery.ready_owner(&clock_at(ready_at - 1)).is_err());
+        assert_eq!(recovery.ready_owner(&clock_at(ready_at)).unwrap(), new_owner);
+    }
+
+    #[test]
+    fn owner_veto_restarts_the_timelock_once() {
+        let guardians = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut recovery = recovery(&guardians, 2);
+        recovery.initiate(guardians[0], Pubkey::new_unique(), &clock_at(0)).unwrap();
+        recovery.approve(guardians[1]).unwrap();
+
+        // The veto clears approvals and restarts the clock
+        assert!(!recovery.cancel(&clock_at(100)).unwrap());
+        assert_eq!(recovery.executable_at, 100 + RECOVERY_TIMELOCK);
+        assert!(recovery.ready_owner(&clock_at(100 + RECOVERY_TIMELOCK)).is_err());
+
+        // Re-approval by the quorum overrides it
+        recovery.approve(guardians[0]).unwrap();
+        recovery.approve(guardians[1]).unwrap();
+        assert!(recovery.cancel(&clock_at(100 + RECOVERY_TIMELOCK)).is_err());
+        assert!(recovery.ready_owner(&clock_at(100 + RECOVERY_TIMELOCK)).is_ok());
+    }
+
+    #[test]
+    fn guardian_changes_wait_out_their_timelock() {
+        let guardians = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let replacement = Pubkey::new_unique();
+        let mut recovery = recovery(&guardians, 1);
+
+        recovery.queue_guardians(vec![replacement], 1, &clock_at(0)).unwrap();
+        assert!(recovery.apply_guardians(&clock_at(GUARDIAN_UPDATE_TIMELOCK - 1)).is_err());
+        recovery.apply_guardians(&clock_at(GUARDIAN_UPDATE_TIMELOCK)).unwrap();
+        assert_eq!(recovery.guardians, vec![replacement]);
+        assert!(recovery.apply_guardians(&clock_at(GUARDIAN_UPDATE_TIMELOCK)).is_err());
+
+        // A recovery started meanwhile drops the queued change
+        recovery.queue_guardians(guardians.to_vec(), 2, &clock_at(0)).unwrap();
+        recovery.initiate(replacement, Pubkey::new_unique(), &clock_at(1)).unwrap();
+        assert!(recovery.apply_guardians(&clock_at(GUARDIAN_UPDATE_TIMELOCK)).is_err());
+        assert_eq!(recovery.guardians, vec![replacement]);
+    }
+}
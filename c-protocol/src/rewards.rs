@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+
+use crate::{CProtocolError, ProtocolState, SoulKey};
+
+pub const PROTOCOL_AUTHORITY_SEED: &[u8] = b"protocol_authority"; // Mint authority PDA for C tokens
+pub const MAX_REWARD_PROOF_LEN: usize = 24; // Enough for ~16M leaves
+
+/// One emission period. Effort points accrue on chain while the epoch is open;
+/// once closed, the authority posts a Merkle root over each worker's points and
+/// workers claim their pro-rata share of the fixed budget.
+#[account]
+#[derive(InitSpace)]
+pub struct RewardEpoch {
+    pub protocol_state: Pubkey,
+    pub epoch: u64,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub emission_budget: u64, // Fixed when the epoch opens
+    pub total_effort_points: u64,
+    pub closed: bool,
+    pub merkle_root: Option<[u8; 32]>, // Commits to (worker, effort points) leaves
+    pub total_claimed: u64,
+    pub distribution_proposals: u32, // Distributions proposed so far; indexes the next one
+    pub bump: u8,
+}
+
+impl RewardEpoch {
+    pub fn new(
+        protocol_state: Pubkey,
+        epoch: u64,
+        start_timestamp: i64,
+        duration: i64,
+        emission_budget: u64,
+        bump: u8,
+    ) -> Self {
+        Self {
+            protocol_state,
+            epoch,
+            start_timestamp,
+            end_timestamp: start_timestamp + duration,
+            emission_budget,
+            total_effort_points: 0,
+            closed: false,
+            merkle_root: None,
+            total_claimed: 0,
+            distribution_proposals: 0,
+            bump,
+        }
+    }
+
+    pub fn accrue(&mut self, effort_points: u64, clock: &Clock) -> Result<()> {
+        require!(
+            !self.closed && clock.unix_timestamp < self.end_timestamp,
+            CProtocolError::RewardEpochEnded
+        );
+        self.total_effort_points = self
+            .total_effort_points
+            .checked_add(effort_points)
+            .ok_or(CProtocolError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Pro-rata share of the budget; rounds down so claims never exceed it.
+    pub fn reward_for(&self, effort_points: u64) -> Result<u64> {
+        require!(
+            effort_points <= self.total_effort_points,
+            CProtocolError::InvalidRewardProof
+        );
+        if self.total_effort_points == 0 {
+            return Ok(0);
+        }
+        let reward = (self.emission_budget as u128 * effort_points as u128)
+            / self.total_effort_points as u128;
+        Ok(reward as u64)
+    }
+
+    /// Budget not yet paid out through either effort-point or distribution claims.
+    pub fn remaining_budget(&self) -> u64 {
+        self.emission_budget.saturating_sub(self.total_claimed)
+    }
+
+    /// Books a payout against the epoch budget. Effort-point claims and
+    /// distribution claims share this counter, so together they never mint
+    /// more than the epoch's emission.
+    pub fn record_claim(&mut self, amount: u64) -> Result<()> {
+        let total_claimed = self
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(CProtocolError::MathOverflow)?;
+        require!(
+            total_claimed <= self.emission_budget,
+            CProtocolError::RewardBudgetExceeded
+        );
+        self.total_claimed = total_claimed;
+        Ok(())
+    }
+}
+
+/// Exists once a worker has claimed from an epoch; its creation blocks double claims.
+#[account]
+#[derive(InitSpace)]
+pub struct EpochClaim {
+    pub reward_epoch: Pubkey,
+    pub claimant: Pubkey,
+    pub effort_points: u64,
+    pub amount: u64,
+}
+
+/// Effort points for a task: its emission value scaled by the SoulKey multiplier.
+pub fn effort_points(emission_amount: u64, emission_multiplier: u64) -> Result<u64> {
+    let points = emission_amount
+        .checked_mul(emission_multiplier)
+        .ok_or(CProtocolError::MathOverflow)?
+        / 100;
+    Ok(points)
+}
+
+impl ProtocolState {
+    /// Once reward epochs start, work earns effort points instead of pending tokens.
+    pub fn reward_epochs_live(&self) -> bool {
+        self.epoch_duration > 0
+    }
+}
+
+/// Books a task's effort points into the open epoch. Before reward epochs
+/// start this does nothing and the accounts may be omitted.
+pub fn accrue_effort(
+    protocol_state: &ProtocolState,
+    reward_epoch: Option<&mut RewardEpoch>,
+    soul_key: Option<&SoulKey>,
+    emission_amount: u64,
+    clock: &Clock,
+) -> Result<u64> {
+    if !protocol_state.reward_epochs_live() {
+        return Ok(0);
+    }
+    let (Some(reward_epoch), Some(soul_key)) = (reward_epoch, soul_key) else {
+        return err!(CProtocolError::RewardEpochAccountsRequired);
+    };
+    let points = effort_points(emission_amount, soul_key.get_emission_multiplier(clock))?;
+    reward_epoch.accrue(points, clock)?;
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock_at(unix_timestamp: i64) -> Clock {
+        Clock { unix_timestamp, ..Clock::default() }
+    }
+
+    #[test]
+    fn effort_accrues_only_while_the_epoch_is_open() {
+        let mut epoch = RewardEpoch::new(Pubkey::new_unique(), 1, 1_000, 100, 1_000_000, 255);
+        epoch.accrue(40, &clock_at(1_000)).unwrap();
+        epoch.accrue(60, &clock_at(1_099)).unwrap();
+        assert_eq!(epoch.total_effort_points, 100);
+
+        assert!(epoch.accrue(1, &clock_at(1_100)).is_err());
+        epoch.closed = true;
+        assert!(epoch.accrue(1, &clock_at(1_050)).is_err());
+        assert_eq!(epoch.total_effort_points, 100);
+
+        epoch.closed = false;
+        epoch.total_effort_points = u64::MAX;
+        assert!(epoch.accrue(1, &clock_at(1_050)).is_err());
+    }
+
+    #[test]
+    fn claims_share_the_epoch_budget() {
+        let mut epoch = RewardEpoch::new(Pubkey::new_unique(), 1, 0, 100, 1_000, 255);
+        epoch.total_effort_points = 3;
+        // Rounds down, so every share together stays within the budget
+        assert_eq!(epoch.reward_for(1).unwrap(), 333);
+        assert!(epoch.reward_for(4).is_err());
+
+        epoch.record_claim(333).unwrap();
+        epoch.record_claim(600).unwrap();
+        assert_eq!(epoch.remaining_budget(), 67);
+        assert!(epoch.record_claim(68).is_err());
+        epoch.record_claim(67).unwrap();
+        assert_eq!(epoch.remaining_budget(), 0);
+    }
+}
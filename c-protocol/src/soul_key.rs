@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
 
 #[account]
+#[derive(InitSpace)]
 pub struct SoulKey {
     pub owner: Pubkey,
+    pub owner_rotations: u16, // Times the SoulKey moved to a new owner through recovery
     pub creation_timestamp: i64,
     pub last_evolution: i64,
     pub evolution_level: u8,
@@ -14,9 +15,11 @@ pub struct SoulKey {
     pub total_work_completed: u64,
     pub work_diversity_score: u64,
     pub consecutive_active_days: u32,
+    pub last_active_day: i64, // UTC day index (unix_timestamp / 86400) of the last task
     pub tokens_earned_lifetime: u64,
     pub tokens_burned_by_decay: u64,
     pub pool_participation_count: u32,
+    pub liquidity_deposits: u32, // LP deposits into partner pools, as indexed by the attestor
     
     // Behavioral patterns
     pub average_task_completion_time: u64,
@@ -27,29 +30,34 @@ pub struct SoulKey {
     // Reputation and social proof
     pub witness_votes_received: u64,
     pub witness_votes_given: u64,
+    pub witness_votes_aligned: u64, // Votes that matched final consensus
+    pub witness_votes_misaligned: u64,
+    pub witness_votes_slashed: u64, // Votes for claims that were later slashed
+    pub trust_epoch: i64, // Epoch of the last trust update
+    pub trust_gained_this_epoch: u64,
     pub referrals_made: u32,
     pub community_endorsements: u32,
     
     // Evolution traits (metadata that changes over time)
     pub dominant_work_type: WorkType,
+    pub work_type_counts: [u32; WORK_TYPE_COUNT], // Tasks completed per WorkType
     pub specialization_depth: u8, // How focused vs generalist
     pub leadership_indicator: bool,
     pub mentor_status: bool,
     
     // Visual/metadata evolution
     pub current_avatar_hash: u64,
-    pub achievement_badges: Vec<Badge>,
+    pub metadata_stale: bool, // Soulbound NFT metadata needs a resync
+    pub badge_mask: u16, // Bit n set = BadgeType variant n earned
+    pub badge_earned_timestamps: [i64; BADGE_COUNT],
+    // Ring buffer of the most recent evolution events; grows only through realloc
+    #[max_len(INITIAL_EVOLUTION_CAPACITY)]
     pub evolution_history: Vec<EvolutionEvent>,
+    pub evolution_capacity: u16,
+    pub evolution_cursor: u16, // Next slot to overwrite once the ring is full
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct Badge {
-    pub badge_type: BadgeType,
-    pub earned_timestamp: i64,
-    pub metadata: String,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
 pub enum BadgeType {
     EarlyAdopter,
     ConsistentWorker,
@@ -63,16 +71,26 @@ pub enum BadgeType {
     WitnessReliable,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+impl BadgeType {
+    pub fn bit(self) -> u16 {
+        1 << (self as u16)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
 pub struct EvolutionEvent {
     pub timestamp: i64,
     pub event_type: EvolutionType,
-    pub trigger_data: String,
+    // Fixed-length code whose meaning depends on event_type: work completed for
+    // LevelUp, old/new WorkType (high/low byte) for SpecializationShift, streak
+    // days for ConsistencyBonus, badge bit for LeadershipUnlock/MentorshipActivated,
+    // rotation count for OwnerRotated
+    pub trigger_code: u64,
     pub old_level: u8,
     pub new_level: u8,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
 pub enum EvolutionType {
     LevelUp,
     SpecializationShift,
@@ -81,26 +99,49 @@ pub enum EvolutionType {
     MentorshipActivated,
     FraudPenalty,
     ConsistencyBonus,
+    OwnerRotated,
 }
 
-use crate::WorkType;
+use crate::{
+    WorkType, BADGE_CATALOGUE, BADGE_COUNT, BASELINE_TRUST, HIGH_VALUE_MIN_ACCURACY_BPS,
+    HIGH_VALUE_MIN_RESOLVED_VOTES, TRUST_EPOCH_SECONDS,
+};
+
+pub const WORK_TYPE_COUNT: usize = 10;
+const MAX_SPECIALIZATION_DEPTH: u64 = 10;
+const LOG2_FRACTION_BITS: u32 = 16;
+const SECONDS_PER_DAY: i64 = 86400;
+const STREAK_MILESTONES: [u32; 4] = [7, 30, 90, 365];
+pub const INITIAL_EVOLUTION_CAPACITY: u16 = 16;
+pub const MAX_EVOLUTION_CAPACITY: u16 = 256;
 
 impl SoulKey {
+    pub const SPACE: usize = Self::space_for(INITIAL_EVOLUTION_CAPACITY);
+
+    /// Account size holding `capacity` evolution events.
+    pub const fn space_for(capacity: u16) -> usize {
+        8 + Self::INIT_SPACE
+            + (capacity as usize - INITIAL_EVOLUTION_CAPACITY as usize) * EvolutionEvent::INIT_SPACE
+    }
+
     pub fn new(owner: Pubkey, clock: &Clock) -> Self {
-        Self {
+        let mut soul_key = Self {
             owner,
+            owner_rotations: 0,
             creation_timestamp: clock.unix_timestamp,
             last_evolution: clock.unix_timestamp,
             evolution_level: 1,
-            trust_score: 1000, // Start with base trust
+            trust_score: BASELINE_TRUST, // Start with base trust
             fraud_resistance: 100,
             
             total_work_completed: 0,
             work_diversity_score: 0,
             consecutive_active_days: 0,
+            last_active_day: 0,
             tokens_earned_lifetime: 0,
             tokens_burned_by_decay: 0,
             pool_participation_count: 0,
+            liquidity_deposits: 0,
             
             average_task_completion_time: 0,
             collaboration_score: 0,
@@ -109,30 +150,50 @@ impl SoulKey {
             
             witness_votes_received: 0,
             witness_votes_given: 0,
+            witness_votes_aligned: 0,
+            witness_votes_misaligned: 0,
+            witness_votes_slashed: 0,
+            trust_epoch: clock.unix_timestamp / TRUST_EPOCH_SECONDS,
+            trust_gained_this_epoch: 0,
             referrals_made: 0,
             community_endorsements: 0,
             
             dominant_work_type: WorkType::OnboardUser, // Default starting type
+            work_type_counts: [0; WORK_TYPE_COUNT],
             specialization_depth: 1,
             leadership_indicator: false,
             mentor_status: false,
             
-            current_avatar_hash: Self::generate_initial_avatar(&owner),
-            achievement_badges: Vec::new(),
+            current_avatar_hash: 0,
+            metadata_stale: false,
+            badge_mask: 0,
+            badge_earned_timestamps: [0; BADGE_COUNT],
             evolution_history: Vec::new(),
-        }
+            evolution_capacity: INITIAL_EVOLUTION_CAPACITY,
+            evolution_cursor: 0,
+        };
+        soul_key.current_avatar_hash = soul_key.avatar_seed();
+        soul_key
     }
     
-    pub fn update_after_work(&mut self, work_record: &crate::WorkRecord, clock: &Clock) -> Result<()> {
+    pub fn update_after_work(
+        &mut self,
+        work_record: &crate::WorkRecord,
+        streak_break_days: u16,
+        clock: &Clock,
+    ) -> Result<()> {
+        // Badges below read trust, so bring it up to date first
+        self.refresh_trust_epoch(clock);
+        
         // Update basic metrics
         self.total_work_completed += 1;
         self.tokens_earned_lifetime += work_record.emission_amount;
         
         // Track work type specialization
-        self.update_specialization(&work_record.work_type);
+        self.update_specialization(&work_record.work_type, clock);
         
         // Update consistency rating
-        self.update_consistency(clock);
+        self.update_consistency(streak_break_days, clock);
         
         // Check for evolution triggers
         self.check_evolution_triggers(clock)?;
@@ -145,7 +206,7 @@ impl SoulKey {
         
         // Decay affects trust score
         let trust_penalty = (decay_amount / 1_000_000).min(50);
-        self.trust_score = self.trust_score.saturating_sub(trust_penalty);
+        self.set_trust(self.trust_score.saturating_sub(trust_penalty));
         
         // Reset consistency if too much decay
         if decay_amount > 10_000_000 { // Large decay indicates inactivity
@@ -161,8 +222,8 @@ impl SoulKey {
             self.witness_votes_received += 1;
         }
         
-        // Voting participation increases trust
-        self.trust_score += 10;
+        // Participation alone earns no trust; it is settled per vote once
+        // consensus is final (see settle_witness_vote)
     }
     
     pub fn add_pool_participation(&mut self) {
@@ -170,165 +231,243 @@ impl SoulKey {
         self.collaboration_score += 50;
     }
     
-    fn update_specialization(&mut self, work_type: &WorkType) {
-        // Track dominant work type
-        // In a full implementation, you'd track frequencies of each type
-        self.dominant_work_type = work_type.clone();
+    /// Raises the LP deposit count to the attestor's indexed total.
+    pub fn record_liquidity_deposits(&mut self, total_deposits: u32, clock: &Clock) -> Result<()> {
+        require!(
+            total_deposits >= self.liquidity_deposits,
+            crate::CProtocolError::LiquidityDepositsDecreased
+        );
+        self.liquidity_deposits = total_deposits;
+        self.check_evolution_triggers(clock)
+    }
+    
+    fn update_specialization(&mut self, work_type: &WorkType, clock: &Clock) {
+        let counts = &mut self.work_type_counts;
+        counts[*work_type as usize] = counts[*work_type as usize].saturating_add(1);
+        
+        // Diversity mirrors the worker account: 100 points per distinct type
+        let distinct_types = counts.iter().filter(|c| **c > 0).count() as u64;
+        self.work_diversity_score = distinct_types * 100;
         
-        // Update specialization depth based on diversity
-        // More diverse = lower specialization, more focused = higher specialization
-        if self.work_diversity_score > 800 {
-            self.specialization_depth = 1; // Generalist
-        } else if self.work_diversity_score < 300 {
-            self.specialization_depth = self.specialization_depth.saturating_add(1).min(10); // Specialist
+        // Dominant type is the most frequent one; ties keep the current type
+        let old_dominant = self.dominant_work_type;
+        let mut dominant = old_dominant;
+        for (index, count) in counts.iter().enumerate() {
+            if *count > counts[dominant as usize] {
+                dominant = work_type_from_index(index);
+            }
+        }
+        
+        // Specialization depth scales inversely with the Shannon entropy of the
+        // type distribution: 10 = single type, 1 = evenly spread across all types
+        let entropy = shannon_entropy(counts);
+        let max_entropy = log2_fixed(WORK_TYPE_COUNT as u64);
+        let spread = ((MAX_SPECIALIZATION_DEPTH - 1) * entropy + max_entropy / 2) / max_entropy;
+        self.specialization_depth = MAX_SPECIALIZATION_DEPTH.saturating_sub(spread).max(1) as u8;
+        
+        if dominant != old_dominant {
+            self.dominant_work_type = dominant;
+            self.metadata_stale = true;
+            self.push_evolution_event(
+                EvolutionType::SpecializationShift,
+                ((old_dominant as u64) << 8) | dominant as u64,
+                self.evolution_level,
+                clock,
+            );
         }
     }
     
-    fn update_consistency(&mut self, clock: &Clock) {
-        let time_since_last = clock.unix_timestamp - self.last_evolution;
-        let days_since = time_since_last / 86400;
+    fn update_consistency(&mut self, streak_break_days: u16, clock: &Clock) {
+        let today = clock.unix_timestamp / SECONDS_PER_DAY;
+        let days_since = today - self.last_active_day;
         
-        if days_since <= 1 {
-            // Active within 24 hours
+        if days_since == 0 && self.consecutive_active_days > 0 {
+            // Already counted today; streaks advance at most once per UTC day
+            return;
+        }
+        
+        if self.consecutive_active_days > 0 && days_since <= streak_break_days.max(1) as i64 {
             self.consecutive_active_days += 1;
             self.consistency_rating += 10;
-        } else if days_since > 7 {
-            // Inactive for over a week
-            self.consecutive_active_days = 0;
-            self.consistency_rating = self.consistency_rating.saturating_sub(50);
+        } else {
+            if self.consecutive_active_days > 0 {
+                // Gap longer than the threshold breaks the streak
+                self.consistency_rating = self.consistency_rating.saturating_sub(50);
+            }
+            self.consecutive_active_days = 1;
         }
+        self.last_active_day = today;
         
-        self.last_evolution = clock.unix_timestamp;
+        if STREAK_MILESTONES.contains(&self.consecutive_active_days) {
+            self.push_evolution_event(
+                EvolutionType::ConsistencyBonus,
+                self.consecutive_active_days as u64,
+                self.evolution_level,
+                clock,
+            );
+        }
     }
     
     fn check_evolution_triggers(&mut self, clock: &Clock) -> Result<()> {
-        let mut evolved = false;
         let old_level = self.evolution_level;
         
         // Level up based on work completed
         let expected_level = ((self.total_work_completed / 100) + 1).min(50) as u8;
         if expected_level > self.evolution_level {
             self.evolution_level = expected_level;
-            evolved = true;
-            
-            // Add level up badge
-            self.achievement_badges.push(Badge {
-                badge_type: BadgeType::ConsistentWorker,
-                earned_timestamp: clock.unix_timestamp,
-                metadata: format!("Reached level {}", expected_level),
-            });
-        }
-        
-        // Trust milestone achievements
-        if self.trust_score >= 5000 && !self.has_badge(&BadgeType::TrustWorthy) {
-            self.achievement_badges.push(Badge {
-                badge_type: BadgeType::TrustWorthy,
-                earned_timestamp: clock.unix_timestamp,
-                metadata: "High trust score achieved".to_string(),
-            });
+            self.push_evolution_event(
+                EvolutionType::LevelUp,
+                self.total_work_completed,
+                old_level,
+                clock,
+            );
         }
         
-        // Consistency achievements
-        if self.consecutive_active_days >= 30 && !self.has_badge(&BadgeType::ConsistentWorker) {
-            self.achievement_badges.push(Badge {
-                badge_type: BadgeType::ConsistentWorker,
-                earned_timestamp: clock.unix_timestamp,
-                metadata: "30 consecutive active days".to_string(),
-            });
-        }
+        let new_badges = self.award_badges(clock);
         
         // Leadership unlock
-        if self.witness_votes_given >= 100 && 
-           self.community_endorsements >= 10 && 
-           !self.leadership_indicator {
+        if new_badges & BadgeType::CommunityBuilder.bit() != 0 && !self.leadership_indicator {
             self.leadership_indicator = true;
-            evolved = true;
-            
-            self.achievement_badges.push(Badge {
-                badge_type: BadgeType::CommunityBuilder,
-                earned_timestamp: clock.unix_timestamp,
-                metadata: "Leadership unlocked".to_string(),
-            });
+            self.push_evolution_event(
+                EvolutionType::LeadershipUnlock,
+                BadgeType::CommunityBuilder.bit() as u64,
+                old_level,
+                clock,
+            );
         }
         
         // Mentor status
-        if self.referrals_made >= 20 && 
-           self.evolution_level >= 10 && 
-           !self.mentor_status {
+        if new_badges & BadgeType::Mentor.bit() != 0 && !self.mentor_status {
             self.mentor_status = true;
-            evolved = true;
-            
-            self.achievement_badges.push(Badge {
-                badge_type: BadgeType::Mentor,
-                earned_timestamp: clock.unix_timestamp,
-                metadata: "Mentor status achieved".to_string(),
-            });
+            self.push_evolution_event(
+                EvolutionType::MentorshipActivated,
+                BadgeType::Mentor.bit() as u64,
+                old_level,
+                clock,
+            );
         }
         
-        // Record evolution event
-        if evolved {
-            self.evolution_history.push(EvolutionEvent {
-                timestamp: clock.unix_timestamp,
-                event_type: EvolutionType::LevelUp,
-                trigger_data: format!("Work: {}, Trust: {}", self.total_work_completed, self.trust_score),
-                old_level,
-                new_level: self.evolution_level,
-            });
-            
+        if self.evolution_level != old_level || new_badges != 0 {
             // Update avatar based on evolution
-            self.current_avatar_hash = Self::generate_evolved_avatar(
-                &self.owner, 
-                self.evolution_level, 
-                &self.dominant_work_type,
-                self.trust_score
-            );
+            self.current_avatar_hash = self.avatar_seed();
+            self.metadata_stale = true;
+        }
+        
+        Ok(())
+    }
+    
+    /// Evaluates every catalogue rule not yet earned and returns the newly earned badge bits.
+    fn award_badges(&mut self, clock: &Clock) -> u16 {
+        let mut new_badges = 0;
+        for rule in BADGE_CATALOGUE.iter() {
+            if self.has_badge(rule.badge) || !rule.is_met(self) {
+                continue;
+            }
+            self.badge_mask |= rule.badge.bit();
+            self.badge_earned_timestamps[rule.badge as usize] = clock.unix_timestamp;
+            new_badges |= rule.badge.bit();
         }
+        new_badges
+    }
+    
+    pub(crate) fn push_evolution_event(
+        &mut self,
+        event_type: EvolutionType,
+        trigger_code: u64,
+        old_level: u8,
+        clock: &Clock,
+    ) {
+        let event = EvolutionEvent {
+            timestamp: clock.unix_timestamp,
+            event_type,
+            trigger_code,
+            old_level,
+            new_level: self.evolution_level,
+        };
+        
+        // Append until capacity is reached, then overwrite the oldest entry
+        if self.evolution_history.len() < self.evolution_capacity as usize {
+            self.evolution_history.push(event);
+        } else {
+            self.evolution_history[self.evolution_cursor as usize] = event;
+            self.evolution_cursor = (self.evolution_cursor + 1) % self.evolution_capacity;
+        }
+        self.last_evolution = clock.unix_timestamp;
+    }
+    
+    /// Moves the reputation to `new_owner`; the caller re-derives the SoulKey PDA.
+    pub fn rotate_owner(&mut self, new_owner: Pubkey, clock: &Clock) {
+        self.owner = new_owner;
+        self.owner_rotations += 1;
+        self.current_avatar_hash = self.avatar_seed();
+        self.metadata_stale = true;
+        self.push_evolution_event(
+            EvolutionType::OwnerRotated,
+            self.owner_rotations as u64,
+            self.evolution_level,
+            clock,
+        );
+    }
+    
+    /// Raises the ring capacity; the account must already be reallocated to `space_for(new_capacity)`.
+    pub fn grow_evolution_capacity(&mut self, new_capacity: u16) -> Result<()> {
+        require!(
+            new_capacity > self.evolution_capacity && new_capacity <= MAX_EVOLUTION_CAPACITY,
+            crate::CProtocolError::InvalidEvolutionCapacity
+        );
         
+        // Put events back in chronological order so new slots append after the newest
+        let cursor = self.evolution_cursor as usize;
+        self.evolution_history.rotate_left(cursor);
+        self.evolution_cursor = 0;
+        self.evolution_capacity = new_capacity;
         Ok(())
     }
     
-    fn has_badge(&self, badge_type: &BadgeType) -> bool {
-        self.achievement_badges.iter().any(|b| &b.badge_type == badge_type)
+    /// Evolution events from oldest to newest.
+    pub fn evolution_events(&self) -> impl Iterator<Item = &EvolutionEvent> {
+        let (newer, older) = self.evolution_history.split_at(self.evolution_cursor as usize);
+        older.iter().chain(newer.iter())
     }
     
-    fn generate_initial_avatar(owner: &Pubkey) -> u64 {
-        // Generate deterministic but unique avatar hash based on pubkey
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        use std::hash::{Hash, Hasher};
-        owner.hash(&mut hasher);
-        hasher.finish()
+    pub fn has_badge(&self, badge_type: BadgeType) -> bool {
+        self.badge_mask & badge_type.bit() != 0
     }
     
-    fn generate_evolved_avatar(
-        owner: &Pubkey, 
-        level: u8, 
-        work_type: &WorkType, 
-        trust: u64
-    ) -> u64 {
-        // Generate evolved avatar incorporating all factors
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        use std::hash::{Hash, Hasher};
-        owner.hash(&mut hasher);
-        level.hash(&mut hasher);
-        work_type.hash(&mut hasher);
-        trust.hash(&mut hasher);
-        hasher.finish()
+    pub fn badge_count(&self) -> u32 {
+        self.badge_mask.count_ones()
     }
     
-    pub fn get_emission_multiplier(&self) -> u64 {
+    /// Seed the avatar renderer derives from this SoulKey: FNV-1a over the
+    /// owner, level, specialty, trust and badges, matching
+    /// `soul_avatar::avatar_seed` so the stored hash names the rendered image.
+    pub fn avatar_seed(&self) -> u64 {
+        let fields: [&[u8]; 4] = [
+            self.owner.as_ref(),
+            &[self.evolution_level, self.dominant_work_type as u8],
+            &self.trust_score.to_le_bytes(),
+            &self.badge_mask.to_le_bytes(),
+        ];
+        fields.iter().flat_map(|field| field.iter()).fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+    
+    pub fn get_emission_multiplier(&self, clock: &Clock) -> u64 {
         // Higher evolved souls get emission bonuses
         let base_multiplier = 100; // 1.0x
         let level_bonus = (self.evolution_level as u64) * 5; // +5% per level
-        let trust_bonus = (self.trust_score / 1000) * 10; // +10% per 1000 trust
+        let trust_bonus = (self.current_trust(clock) / 1000) * 10; // +10% per 1000 trust
         let consistency_bonus = if self.consecutive_active_days >= 7 { 25 } else { 0 }; // +25% for weekly consistency
         
         base_multiplier + level_bonus + trust_bonus + consistency_bonus
     }
     
-    pub fn can_witness_high_value(&self) -> bool {
+    pub fn can_witness_high_value(&self, clock: &Clock) -> bool {
         self.evolution_level >= 5 && 
-        self.trust_score >= 2000 && 
-        self.witness_votes_given >= 10
+        self.current_trust(clock) >= 2000 && 
+        self.resolved_witness_votes() >= HIGH_VALUE_MIN_RESOLVED_VOTES &&
+        self.witness_accuracy_bps() >= HIGH_VALUE_MIN_ACCURACY_BPS
     }
     
     pub fn get_reputation_summary(&self) -> String {
@@ -342,6 +481,10 @@ impl SoulKey {
         )
     }
     
+    pub fn specialty(&self) -> &str {
+        self.format_work_type(&self.dominant_work_type)
+    }
+    
     fn format_work_type(&self, work_type: &WorkType) -> &str {
         match work_type {
             WorkType::OnboardUser => "Onboarder",
@@ -356,4 +499,55 @@ impl SoulKey {
             WorkType::UserSupport => "Support Specialist",
         }
     }
-}
\ No newline at end of file
+}
+
+fn work_type_from_index(index: usize) -> WorkType {
+    match index {
+        0 => WorkType::OnboardUser,
+        1 => WorkType::CreateContent,
+        2 => WorkType::WriteCode,
+        3 => WorkType::ReferClient,
+        4 => WorkType::CloseDeal,
+        5 => WorkType::CommunityManagement,
+        6 => WorkType::BugReport,
+        7 => WorkType::Documentation,
+        8 => WorkType::Marketing,
+        _ => WorkType::UserSupport,
+    }
+}
+
+/// Shannon entropy (bits) of the count distribution, in 16.16 fixed point.
+fn shannon_entropy(counts: &[u32]) -> u64 {
+    let total: u64 = counts.iter().map(|c| *c as u64).sum();
+    if total == 0 {
+        return 0;
+    }
+    // H = log2(N) - sum(c * log2(c)) / N
+    let weighted: u128 = counts
+        .iter()
+        .filter(|c| **c > 0)
+        .map(|c| *c as u128 * log2_fixed(*c as u64) as u128)
+        .sum();
+    log2_fixed(total).saturating_sub((weighted / total as u128) as u64)
+}
+
+/// log2(x) in 16.16 fixed point using integer-only repeated squaring; x must be >= 1.
+fn log2_fixed(x: u64) -> u64 {
+    let integer_part = 63 - x.leading_zeros();
+    let mut result = (integer_part as u64) << LOG2_FRACTION_BITS;
+    
+    // Normalize x into [1, 2) as a Q32 value
+    let mut y: u128 = if integer_part <= 32 {
+        (x as u128) << (32 - integer_part)
+    } else {
+        (x as u128) >> (integer_part - 32)
+    };
+    for bit in (0..LOG2_FRACTION_BITS).rev() {
+        y = (y * y) >> 32;
+        if y >= 2 << 32 {
+            y >>= 1;
+            result += 1 << bit;
+        }
+    }
+    result
+}
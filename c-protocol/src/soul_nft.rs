@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{metadata_pointer, permanent_delegate, ExtensionType, StateWithExtensions},
+    state::{Account as Token2022Account, Mint as Token2022Mint},
+};
+use spl_token_metadata_interface::{instruction as metadata_instruction, state::Field};
+
+use crate::SoulKey;
+
+pub const SOUL_NFT_NAME: &str = "SoulKey";
+pub const SOUL_NFT_SYMBOL: &str = "SOUL";
+pub const MAX_SOUL_NFT_URI_LEN: usize = 200;
+
+// Attribute keys written to the token metadata; values are capped so the mint
+// can be funded once for its largest possible size
+pub const SOUL_NFT_ATTRIBUTE_KEYS: [&str; 5] = ["level", "trust", "specialty", "badges", "avatar"];
+const MAX_ATTRIBUTE_VALUE_LEN: usize = 20;
+
+/// Mint account length before the variable-length metadata TLV entry is appended.
+pub fn soul_mint_base_len() -> Result<usize> {
+    let len = ExtensionType::try_calculate_account_len::<Token2022Mint>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::MetadataPointer,
+    ])?;
+    Ok(len)
+}
+
+/// Upper bound of the mint account length once metadata and every attribute are written.
+pub fn soul_mint_max_len(uri: &str) -> Result<usize> {
+    let attributes_len: usize = SOUL_NFT_ATTRIBUTE_KEYS
+        .iter()
+        .map(|key| 4 + key.len() + 4 + MAX_ATTRIBUTE_VALUE_LEN)
+        .sum();
+    let metadata_len = 8 + 4 // TLV discriminator and length
+        + 32 // update_authority
+        + 32 // mint
+        + 4 + SOUL_NFT_NAME.len()
+        + 4 + SOUL_NFT_SYMBOL.len()
+        + 4 + uri.len()
+        + 4 + attributes_len;
+    Ok(soul_mint_base_len()? + metadata_len)
+}
+
+pub fn soul_nft_attributes(soul_key: &SoulKey) -> [(&'static str, String); 5] {
+    [
+        ("level", soul_key.evolution_level.to_string()),
+        ("trust", soul_key.trust_score.to_string()),
+        ("specialty", soul_key.specialty().to_string()),
+        ("badges", soul_key.badge_mask.to_string()),
+        ("avatar", format!("{:016x}", soul_key.current_avatar_hash)),
+    ]
+}
+
+/// Configures the non-transferable, permanent-delegate and metadata-pointer
+/// extensions; must run before the mint itself is initialized.
+pub fn initialize_soul_mint_extensions<'info>(
+    token_program: &AccountInfo<'info>,
+    soul_mint: &AccountInfo<'info>,
+    soul_key: &Pubkey,
+) -> Result<()> {
+    let non_transferable_ix = spl_token_2022::instruction::initialize_non_transferable_mint(
+        token_program.key,
+        soul_mint.key,
+    )?;
+    invoke(&non_transferable_ix, std::slice::from_ref(soul_mint))?;
+
+    // Lets the SoulKey PDA burn the token when recovery rotates the owner
+    let permanent_delegate_ix = spl_token_2022::instruction::initialize_permanent_delegate(
+        token_program.key,
+        soul_mint.key,
+        soul_key,
+    )?;
+    invoke(&permanent_delegate_ix, std::slice::from_ref(soul_mint))?;
+
+    // Metadata lives on the mint itself, updatable by the SoulKey PDA
+    let metadata_pointer_ix = metadata_pointer::instruction::initialize(
+        token_program.key,
+        soul_mint.key,
+        Some(*soul_key),
+        Some(*soul_mint.key),
+    )?;
+    invoke(&metadata_pointer_ix, std::slice::from_ref(soul_mint))?;
+    Ok(())
+}
+
+pub fn initialize_soul_metadata<'info>(
+    token_program: &AccountInfo<'info>,
+    soul_mint: &AccountInfo<'info>,
+    soul_key: &AccountInfo<'info>,
+    uri: String,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let initialize_ix = metadata_instruction::initialize(
+        token_program.key,
+        soul_mint.key,
+        soul_key.key,
+        soul_mint.key,
+        soul_key.key,
+        SOUL_NFT_NAME.to_string(),
+        SOUL_NFT_SYMBOL.to_string(),
+        uri,
+    );
+    invoke_signed(
+        &initialize_ix,
+        &[soul_mint.clone(), soul_key.clone(), soul_mint.clone(), soul_key.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+pub fn write_soul_attributes<'info>(
+    token_program: &AccountInfo<'info>,
+    soul_mint: &AccountInfo<'info>,
+    soul_key_info: &AccountInfo<'info>,
+    soul_key: &SoulKey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    for (key, value) in soul_nft_attributes(soul_key) {
+        let update_ix = metadata_instruction::update_field(
+            token_program.key,
+            soul_mint.key,
+            soul_key_info.key,
+            Field::Key(key.to_string()),
+            value,
+        );
+        invoke_signed(
+            &update_ix,
+            &[soul_mint.clone(), soul_key_info.clone()],
+            signer_seeds,
+        )?;
+    }
+    Ok(())
+}
+
+/// Retires the soulbound NFT of a SoulKey whose owner was rotated away and
+/// returns whether the token was burned. Mints from before the permanent
+/// delegate extension cannot be burned by the program, so their metadata URI
+/// is cleared instead.
+pub fn retire_soul_nft<'info>(
+    token_program: &AccountInfo<'info>,
+    soul_mint: &AccountInfo<'info>,
+    soul_token_account: &AccountInfo<'info>,
+    soul_key_info: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<bool> {
+    if soul_mint.data_is_empty() {
+        return Ok(false);
+    }
+
+    let burnable = {
+        let data = soul_mint.try_borrow_data()?;
+        let mint = StateWithExtensions::<Token2022Mint>::unpack(&data)?;
+        permanent_delegate::get_permanent_delegate(&mint) == Some(*soul_key_info.key)
+    };
+    let balance = if soul_token_account.data_is_empty() {
+        0
+    } else {
+        let data = soul_token_account.try_borrow_data()?;
+        StateWithExtensions::<Token2022Account>::unpack(&data)?.base.amount
+    };
+
+    let burned = burnable && balance > 0;
+    if burned {
+        let burn_ix = spl_token_2022::instruction::burn(
+            token_program.key,
+            soul_token_account.key,
+            soul_mint.key,
+            soul_key_info.key,
+            &[],
+            balance,
+        )?;
+        invoke_signed(
+            &burn_ix,
+            &[soul_token_account.clone(), soul_mint.clone(), soul_key_info.clone()],
+            signer_seeds,
+        )?;
+    } else {
+        let update_ix = metadata_instruction::update_field(
+            token_program.key,
+            soul_mint.key,
+            soul_key_info.key,
+            Field::Uri,
+            String::new(),
+        );
+        invoke_signed(
+            &update_ix,
+            &[soul_mint.clone(), soul_key_info.clone()],
+            signer_seeds,
+        )?;
+    }
+    Ok(burned)
+}
@@ -0,0 +1,337 @@
+use anchor_lang::prelude::*;
+
+use crate::{CProtocolError, EvolutionType, ProtocolState, SoulKey};
+
+pub const BASELINE_TRUST: u64 = 1000;
+pub const MAX_TRUST: u64 = 10_000;
+pub const TRUST_EPOCH_SECONDS: i64 = 86400; // Same daily epoch as token decay
+pub const MAX_TRUST_GAIN_PER_EPOCH: u64 = 100;
+pub const ALIGNED_VOTE_REWARD: u64 = 20;
+pub const MISALIGNED_VOTE_PENALTY: u64 = 30;
+pub const SLASHED_VOTE_PENALTY: u64 = 250;
+pub const TRUST_REVERSION_BPS: u64 = 50; // Share of the distance to baseline removed per epoch
+const MAX_REVERSION_EPOCHS: i64 = 365;
+
+// High-value witnessing requires a proven track record, not just volume
+pub const HIGH_VALUE_MIN_RESOLVED_VOTES: u64 = 10;
+pub const HIGH_VALUE_MIN_ACCURACY_BPS: u64 = 8000;
+
+// Absolute floor on consensus weight, kept from the original fixed threshold so
+// a thin or empty witness set can never reach consensus on its own
+pub const MIN_CONSENSUS_WEIGHT: u64 = 6_000;
+
+pub const WITNESS_VOTE_SEED: &[u8] = b"witness_vote";
+pub const WITNESS_REGISTRATION_SEED: &[u8] = b"witness";
+
+/// A SoulKey's seat in the witness set. The weight is its trust as of the last
+/// refresh, or zero once it no longer qualifies; the protocol keeps the sum so
+/// consensus can be measured as a share of all witness trust.
+#[account]
+#[derive(InitSpace)]
+pub struct WitnessRegistration {
+    pub soul_key: Pubkey,
+    pub weight: u64,
+    pub seeded: bool, // Registered by the authority to bootstrap the witness set
+    pub bump: u8,
+}
+
+impl WitnessRegistration {
+    /// Weight the witness qualifies for now. Seeded witnesses count at their
+    /// trust while they build a record, and keep counting only while that
+    /// record meets the high-value accuracy bar; everyone else must qualify
+    /// for high-value witnessing outright.
+    pub fn eligible_weight(&self, soul_key: &SoulKey, clock: &Clock) -> u64 {
+        let seed_in_good_standing = self.seeded
+            && (soul_key.resolved_witness_votes() < HIGH_VALUE_MIN_RESOLVED_VOTES
+                || soul_key.witness_accuracy_bps() >= HIGH_VALUE_MIN_ACCURACY_BPS);
+        if seed_in_good_standing {
+            soul_key.current_trust(clock)
+        } else {
+            soul_key.witness_weight(clock)
+        }
+    }
+}
+
+impl ProtocolState {
+    /// Replaces a witness's weight in the protocol-wide total.
+    pub fn reweigh_witness(&mut self, registration: &mut WitnessRegistration, weight: u64) {
+        self.total_witness_trust = self.total_witness_trust - registration.weight + weight;
+        registration.weight = weight;
+    }
+
+    /// Drops a registration's weight from the witness set, e.g. when its
+    /// SoulKey moves to a new owner.
+    pub fn deregister_witness(&mut self, registration: &WitnessRegistration) {
+        self.total_witness_trust -= registration.weight;
+    }
+
+    /// Approval weight needed for consensus: `witness_threshold` basis points
+    /// of the total registered witness trust, rounded up, and never less than
+    /// MIN_CONSENSUS_WEIGHT. Fails while no witness is registered.
+    pub fn witness_quorum(&self) -> Result<u64> {
+        require!(self.total_witness_trust > 0, CProtocolError::NoRegisteredWitnesses);
+        let share = (self.total_witness_trust as u128 * self.witness_threshold as u128).div_ceil(10_000) as u64;
+        Ok(share.max(MIN_CONSENSUS_WEIGHT))
+    }
+}
+
+/// A witness's approval of a distribution. Its outcome is settled into the
+/// witness's trust exactly once, by comparing it with how the proposal resolved.
+#[account]
+#[derive(InitSpace)]
+pub struct WitnessVote {
+    pub subject: Pubkey, // Distribution the vote was cast on
+    pub proposal_index: u32, // Distribution::proposal_index at the time of the vote
+    pub soul_key: Pubkey,
+    pub cast_timestamp: i64,
+    pub settled: bool,
+    pub bump: u8,
+}
+
+impl WitnessVote {
+    pub fn settle(&mut self) -> Result<()> {
+        require!(!self.settled, CProtocolError::WitnessVoteAlreadySettled);
+        self.settled = true;
+        Ok(())
+    }
+}
+
+impl SoulKey {
+    /// Settles a witness vote once its claim reaches final consensus.
+    pub fn settle_witness_vote(&mut self, aligned: bool, clock: &Clock) {
+        self.refresh_trust_epoch(clock);
+        
+        if aligned {
+            self.witness_votes_aligned += 1;
+            self.gain_trust(ALIGNED_VOTE_REWARD, clock);
+        } else {
+            self.witness_votes_misaligned += 1;
+            self.set_trust(self.trust_score.saturating_sub(MISALIGNED_VOTE_PENALTY));
+        }
+    }
+    
+    /// Penalizes a vote in favour of a claim that was later slashed.
+    pub fn penalize_slashed_vote(&mut self, clock: &Clock) {
+        self.refresh_trust_epoch(clock);
+        
+        self.witness_votes_slashed += 1;
+        self.set_trust(self.trust_score.saturating_sub(SLASHED_VOTE_PENALTY));
+        self.fraud_resistance = self.fraud_resistance.saturating_sub(10);
+        self.push_evolution_event(
+            EvolutionType::FraudPenalty,
+            self.witness_votes_slashed,
+            self.evolution_level,
+            clock,
+        );
+    }
+    
+    /// Share of resolved witness votes that matched consensus, in basis points.
+    pub fn witness_accuracy_bps(&self) -> u64 {
+        let resolved = self.resolved_witness_votes();
+        if resolved == 0 {
+            return 0;
+        }
+        self.witness_votes_aligned * 10_000 / resolved
+    }
+    
+    pub fn resolved_witness_votes(&self) -> u64 {
+        self.witness_votes_aligned + self.witness_votes_misaligned + self.witness_votes_slashed
+    }
+    
+    fn gain_trust(&mut self, amount: u64, clock: &Clock) {
+        let allowance = MAX_TRUST_GAIN_PER_EPOCH.saturating_sub(self.trust_gained_this_epoch);
+        let gain = amount.min(allowance).min(MAX_TRUST.saturating_sub(self.trust_score));
+        if gain == 0 {
+            return;
+        }
+        
+        let old_trust = self.trust_score;
+        self.set_trust(self.trust_score + gain);
+        self.trust_gained_this_epoch += gain;
+        
+        if self.trust_score / 1000 > old_trust / 1000 {
+            self.push_evolution_event(
+                EvolutionType::TrustIncrease,
+                self.trust_score,
+                self.evolution_level,
+                clock,
+            );
+        }
+    }
+    
+    /// Weight this SoulKey carries as a witness: its trust while it qualifies
+    /// for high-value witnessing, zero otherwise.
+    pub fn witness_weight(&self, clock: &Clock) -> u64 {
+        if self.can_witness_high_value(clock) {
+            self.current_trust(clock)
+        } else {
+            0
+        }
+    }
+    
+    /// Trust including the reversion owed for epochs since the last refresh.
+    /// Stored trust only catches up on writes, so reads go through this.
+    pub fn current_trust(&self, clock: &Clock) -> u64 {
+        reverted_trust(self.trust_score, self.elapsed_trust_epochs(clock))
+    }
+    
+    /// Starts a new trust epoch if one has passed, pulling trust back toward the
+    /// baseline for every elapsed epoch and resetting the per-epoch gain cap.
+    pub fn refresh_trust_epoch(&mut self, clock: &Clock) {
+        let elapsed = self.elapsed_trust_epochs(clock);
+        if elapsed == 0 {
+            return;
+        }
+        
+        self.set_trust(reverted_trust(self.trust_score, elapsed));
+        self.trust_epoch = clock.unix_timestamp / TRUST_EPOCH_SECONDS;
+        self.trust_gained_this_epoch = 0;
+    }
+    
+    fn elapsed_trust_epochs(&self, clock: &Clock) -> i64 {
+        let current_epoch = clock.unix_timestamp / TRUST_EPOCH_SECONDS;
+        (current_epoch - self.trust_epoch).clamp(0, MAX_REVERSION_EPOCHS)
+    }
+    
+    /// Every trust change leaves the NFT's trust attribute out of date.
+    pub(crate) fn set_trust(&mut self, trust_score: u64) {
+        if trust_score != self.trust_score {
+            self.trust_score = trust_score;
+            self.metadata_stale = true;
+        }
+    }
+}
+
+fn reverted_trust(mut trust_score: u64, epochs: i64) -> u64 {
+    for _ in 0..epochs {
+        if trust_score > BASELINE_TRUST {
+            trust_score -= ((trust_score - BASELINE_TRUST) * TRUST_REVERSION_BPS / 10_000).max(1);
+        } else if trust_score < BASELINE_TRUST {
+            trust_score += ((BASELINE_TRUST - trust_score) * TRUST_REVERSION_BPS / 10_000).max(1);
+        }
+    }
+    trust_score
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{WorkRecord, WorkType};
+
+    fn clock_at_day(day: i64) -> Clock {
+        Clock {
+            unix_timestamp: day * TRUST_EPOCH_SECONDS + 3600,
+            ..Clock::default()
+        }
+    }
+
+    pub(crate) fn protocol_state() -> ProtocolState {
+        ProtocolState {
+            total_work_recorded: 0,
+            total_tokens_emitted: 0,
+            decay_rate: 0,
+            witness_threshold: 6000,
+            min_stake_to_emit: 0,
+            authority: Pubkey::default(),
+            streak_break_days: 1,
+            attestor: Pubkey::default(),
+            c_token_mint: Pubkey::default(),
+            epoch_emission: 0,
+            epoch_duration: 0,
+            current_epoch: 0,
+            decay_destination: Pubkey::default(),
+            vesting_schedules: Default::default(),
+            total_witness_trust: 0,
+        }
+    }
+
+    fn do_work(soul_key: &mut SoulKey, clock: &Clock) {
+        let record = WorkRecord {
+            work_type: WorkType::WriteCode,
+            effort_weight: 100,
+            timestamp: clock.unix_timestamp,
+            emission_amount: 2_000_000,
+            metadata_hash: 0,
+            organization: None,
+            unminted_amount: 0,
+        };
+        soul_key.update_after_work(&record, 1, clock).unwrap();
+    }
+
+    #[test]
+    fn seeded_witness_walks_from_genesis_to_registered() {
+        let mut state = protocol_state();
+        let mut clock = clock_at_day(1);
+        let mut soul_key = SoulKey::new(Pubkey::new_unique(), &clock);
+        soul_key.trust_epoch = 1;
+
+        // A fresh SoulKey cannot register on its own, and consensus is closed
+        assert_eq!(soul_key.witness_weight(&clock), 0);
+        assert!(state.witness_quorum().is_err());
+
+        // The authority seeds it at its current trust
+        let mut registration = WitnessRegistration {
+            soul_key: Pubkey::new_unique(),
+            weight: 0,
+            seeded: true,
+            bump: 0,
+        };
+        let weight = registration.eligible_weight(&soul_key, &clock);
+        assert_eq!(weight, BASELINE_TRUST);
+        state.reweigh_witness(&mut registration, weight);
+        assert_eq!(state.total_witness_trust, BASELINE_TRUST);
+        assert_eq!(state.witness_quorum().unwrap(), MIN_CONSENSUS_WEIGHT);
+
+        // Daily work and aligned votes build level, trust and a resolved record
+        for day in 1..=30 {
+            clock = clock_at_day(day);
+            for _ in 0..15 {
+                do_work(&mut soul_key, &clock);
+            }
+            for _ in 0..5 {
+                soul_key.settle_witness_vote(true, &clock);
+            }
+            let weight = registration.eligible_weight(&soul_key, &clock);
+            assert!(weight > 0, "seeded witness dropped out on day {day}");
+            state.reweigh_witness(&mut registration, weight);
+        }
+
+        // It now qualifies without the seed, at the same weight
+        assert!(soul_key.resolved_witness_votes() >= HIGH_VALUE_MIN_RESOLVED_VOTES);
+        assert!(soul_key.can_witness_high_value(&clock));
+        let own_weight = soul_key.witness_weight(&clock);
+        let unseeded = WitnessRegistration { seeded: false, ..registration.clone() };
+        assert_eq!(unseeded.eligible_weight(&soul_key, &clock), own_weight);
+        assert_eq!(registration.eligible_weight(&soul_key, &clock), own_weight);
+        assert_eq!(state.total_witness_trust, own_weight);
+        assert!(own_weight >= 2000);
+    }
+
+    #[test]
+    fn witness_quorum_takes_the_threshold_share_with_a_floor() {
+        let mut state = protocol_state();
+        assert!(state.witness_quorum().is_err());
+
+        state.total_witness_trust = 5_000;
+        assert_eq!(state.witness_quorum().unwrap(), MIN_CONSENSUS_WEIGHT);
+
+        // 60% of 20_001 is 12_000.6, rounded up
+        state.total_witness_trust = 20_001;
+        assert_eq!(state.witness_quorum().unwrap(), 12_001);
+    }
+
+    #[test]
+    fn graduated_seed_without_accuracy_drops_out() {
+        let clock = clock_at_day(1);
+        let mut soul_key = SoulKey::new(Pubkey::new_unique(), &clock);
+        soul_key.trust_epoch = 1;
+        soul_key.witness_votes_misaligned = HIGH_VALUE_MIN_RESOLVED_VOTES;
+        let registration = WitnessRegistration {
+            soul_key: Pubkey::new_unique(),
+            weight: 0,
+            seeded: true,
+            bump: 0,
+        };
+        assert_eq!(registration.eligible_weight(&soul_key, &clock), 0);
+    }
+}
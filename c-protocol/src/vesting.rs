@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+
+use crate::{CProtocolError, ProtocolState};
+
+/// Cliff plus linear release, measured from the mint. A zero duration means
+/// rewards are minted liquid, which is the default for every work type.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct VestingSchedule {
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+    pub early_exit_penalty_bps: u16, // Share of the unvested balance forfeited on early exit
+}
+
+impl VestingSchedule {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.cliff_seconds >= 0
+                && self.cliff_seconds <= self.duration_seconds
+                && self.early_exit_penalty_bps <= 10_000,
+            CProtocolError::InvalidVestingSchedule
+        );
+        Ok(())
+    }
+
+    pub fn is_vested(&self) -> bool {
+        self.duration_seconds > 0
+    }
+}
+
+impl ProtocolState {
+    /// Epoch and distribution rewards are not tied to one work type, so they
+    /// vest on the longest schedule any work type uses; claiming through the
+    /// pool is never a way around vesting.
+    pub fn pooled_vesting_schedule(&self) -> VestingSchedule {
+        self.vesting_schedules
+            .iter()
+            .copied()
+            .max_by_key(|schedule| (schedule.duration_seconds, schedule.cliff_seconds))
+            .unwrap_or_default()
+    }
+}
+
+/// Minted rewards held by the protocol until they vest. Tokens sit in a vault
+/// owned by the protocol authority PDA, which also signs releases.
+#[account]
+#[derive(InitSpace)]
+pub struct VestingEscrow {
+    pub worker: Pubkey, // Worker the tokens were minted for; seeds the escrow and vault PDAs
+    pub beneficiary: Pubkey, // Follows the SoulKey through recovery
+    pub vesting_id: u64, // Worker's vesting_count at the time of the mint
+    pub schedule: VestingSchedule,
+    pub start_timestamp: i64,
+    pub total_amount: u64,
+    pub released_amount: u64,
+    pub vault_bump: u8,
+    pub bump: u8,
+}
+
+impl VestingEscrow {
+    pub fn vested_amount(&self, clock: &Clock) -> u64 {
+        let elapsed = clock.unix_timestamp - self.start_timestamp;
+        if elapsed < self.schedule.cliff_seconds {
+            return 0;
+        }
+        if elapsed >= self.schedule.duration_seconds {
+            return self.total_amount;
+        }
+        (self.total_amount as u128 * elapsed as u128 / self.schedule.duration_seconds as u128) as u64
+    }
+
+    /// Vested tokens not yet released; marks them as released.
+    pub fn take_releasable(&mut self, clock: &Clock) -> u64 {
+        let releasable = self.vested_amount(clock) - self.released_amount;
+        self.released_amount += releasable;
+        releasable
+    }
+
+    /// Splits the unvested remainder into (paid to the beneficiary, forfeited).
+    pub fn exit_split(&self) -> (u64, u64) {
+        let unvested = self.total_amount - self.released_amount;
+        let penalty = (unvested as u128 * self.schedule.early_exit_penalty_bps as u128 / 10_000) as u64;
+        (unvested - penalty, penalty)
+    }
+
+    pub fn address(worker: &Pubkey, vesting_id: u64) -> Pubkey {
+        Pubkey::find_program_address(&[b"vesting", worker.as_ref(), &vesting_id.to_le_bytes()], &crate::ID).0
+    }
+}
+
+/// Hands every open escrow of `old_owner` to `new_owner` during recovery.
+/// `escrows` starts with the escrow addresses for the old worker's vesting ids
+/// `0..vesting_count` in order, closed ones included so none can be left
+/// behind, followed by any escrows the old owner inherited from an earlier
+/// recovery. Returns how many were handed over.
+pub fn hand_over_vesting_escrows(
+    escrows: &[AccountInfo],
+    old_owner: &Pubkey,
+    new_owner: &Pubkey,
+    vesting_count: u64,
+) -> Result<u32> {
+    require!(escrows.len() as u64 >= vesting_count, CProtocolError::VestingAccountsMismatch);
+    let mut handed_over = 0;
+    for (index, info) in escrows.iter().enumerate() {
+        let own = (index as u64) < vesting_count;
+        if own {
+            require_keys_eq!(
+                info.key(),
+                VestingEscrow::address(old_owner, index as u64),
+                CProtocolError::VestingAccountsMismatch
+            );
+            if info.owner != &crate::ID || info.data_is_empty() {
+                continue; // Already exited
+            }
+        }
+        require!(info.owner == &crate::ID && info.is_writable, CProtocolError::VestingAccountsMismatch);
+        let mut data = info.try_borrow_mut_data()?;
+        let mut escrow = VestingEscrow::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(escrow.beneficiary, *old_owner, CProtocolError::VestingAccountsMismatch);
+        escrow.beneficiary = *new_owner;
+        escrow.try_serialize(&mut &mut data[..])?;
+        handed_over += 1;
+    }
+    Ok(handed_over)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escrow_data(worker: Pubkey, beneficiary: Pubkey, vesting_id: u64) -> Vec<u8> {
+        let escrow = VestingEscrow {
+            worker,
+            beneficiary,
+            vesting_id,
+            schedule: VestingSchedule { cliff_seconds: 0, duration_seconds: 100, early_exit_penalty_bps: 0 },
+            start_timestamp: 0,
+            total_amount: 1_000,
+            released_amount: 0,
+            vault_bump: 255,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        escrow.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn escrow(schedule: VestingSchedule) -> VestingEscrow {
+        let data = escrow_data(Pubkey::new_unique(), Pubkey::new_unique(), 0);
+        VestingEscrow { schedule, ..VestingEscrow::try_deserialize(&mut &data[..]).unwrap() }
+    }
+
+    fn clock_at(unix_timestamp: i64) -> Clock {
+        Clock { unix_timestamp, ..Clock::default() }
+    }
+
+    #[test]
+    fn releases_follow_the_cliff_and_linear_schedule() {
+        let mut escrow = escrow(VestingSchedule { cliff_seconds: 20, duration_seconds: 100, early_exit_penalty_bps: 0 });
+        assert_eq!(escrow.take_releasable(&clock_at(19)), 0);
+        assert_eq!(escrow.take_releasable(&clock_at(20)), 200);
+        assert_eq!(escrow.take_releasable(&clock_at(50)), 300);
+        assert_eq!(escrow.take_releasable(&clock_at(50)), 0);
+        assert_eq!(escrow.take_releasable(&clock_at(500)), 500);
+        assert_eq!(escrow.released_amount, escrow.total_amount);
+    }
+
+    #[test]
+    fn early_exit_forfeits_a_share_of_the_unvested_balance() {
+        let mut escrow = escrow(VestingSchedule { cliff_seconds: 0, duration_seconds: 100, early_exit_penalty_bps: 2_500 });
+        assert_eq!(escrow.exit_split(), (750, 250));
+        escrow.take_releasable(&clock_at(60));
+        assert_eq!(escrow.exit_split(), (300, 100));
+        escrow.take_releasable(&clock_at(100));
+        assert_eq!(escrow.exit_split(), (0, 0));
+    }
+
+    #[test]
+    fn pooled_rewards_vest_on_the_longest_schedule() {
+        let mut protocol_state = crate::trust::tests::protocol_state();
+        assert!(!protocol_state.pooled_vesting_schedule().is_vested());
+
+        let slow = VestingSchedule { cliff_seconds: 10, duration_seconds: 1_000, early_exit_penalty_bps: 5_000 };
+        protocol_state.vesting_schedules[1] =
+            VestingSchedule { cliff_seconds: 50, duration_seconds: 100, early_exit_penalty_bps: 0 };
+        protocol_state.vesting_schedules[3] = slow;
+        assert_eq!(protocol_state.pooled_vesting_schedule(), slow);
+    }
+
+    #[test]
+    fn recovery_hands_over_open_and_inherited_escrows() {
+        let old_owner = Pubkey::new_unique();
+        let new_owner = Pubkey::new_unique();
+        let earlier_owner = Pubkey::new_unique();
+        let program_id = crate::ID;
+        let system_id = anchor_lang::system_program::ID;
+
+        // Vesting id 0 was exited, id 1 is open, and one escrow came from an earlier recovery
+        let keys = [
+            VestingEscrow::address(&old_owner, 0),
+            VestingEscrow::address(&old_owner, 1),
+            VestingEscrow::address(&earlier_owner, 0),
+        ];
+        let mut lamports = [0, 1, 1];
+        let mut data = [
+            Vec::new(),
+            escrow_data(old_owner, old_owner, 1),
+            escrow_data(earlier_owner, old_owner, 0),
+        ];
+        let owners = [system_id, program_id, program_id];
+        let [l0, l1, l2] = &mut lamports;
+        let [d0, d1, d2] = &mut data;
+        let infos = [
+            AccountInfo::new(&keys[0], false, true, l0, d0, &owners[0], false, 0),
+            AccountInfo::new(&keys[1], false, true, l1, d1, &owners[1], false, 0),
+            AccountInfo::new(&keys[2], false, true, l2, d2, &owners[2], false, 0),
+        ];
+
+        assert!(hand_over_vesting_escrows(&infos[1..], &old_owner, &new_owner, 2).is_err());
+        assert_eq!(hand_over_vesting_escrows(&infos, &old_owner, &new_owner, 2).unwrap(), 2);
+        for info in &infos[1..] {
+            let escrow = VestingEscrow::try_deserialize(&mut &info.try_borrow_data().unwrap()[..]).unwrap();
+            assert_eq!(escrow.beneficiary, new_owner);
+        }
+        // Already handed over, so a replay finds them no longer the old owner's
+        assert!(hand_over_vesting_escrows(&infos, &old_owner, &new_owner, 2).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+//! Off-chain verification of signed SoulKey reputation attestations.
+//!
+//! An attestation is a `c_protocol::ReputationAttestation` snapshot signed by
+//! the protocol's registered attestor key. Partners check it in two steps:
+//!
+//! 1. [`Verifier::verify`] checks the signature against a trusted attestor,
+//!    that the snapshot names the expected program and the owner's SoulKey
+//!    PDA, and that it is recent enough. No RPC access is needed.
+//! 2. Optionally, [`Verifier::verify_against_account`] compares the snapshot
+//!    with SoulKey account data fetched from an RPC node the partner trusts,
+//!    after checking the data came from the attested SoulKey address.
+
+use std::fmt;
+
+use anchor_lang::prelude::{borsh, Pubkey};
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, AnchorSerialize};
+use c_protocol::{ReputationAttestation, SoulKey, ATTESTATION_VERSION};
+use ed25519_dalek::{PublicKey, Signature};
+
+/// An attestation together with the attestor that signed it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignedAttestation {
+    pub attestation: ReputationAttestation,
+    pub attestor: Pubkey,
+    pub signature: [u8; 64],
+}
+
+impl SignedAttestation {
+    /// Borsh encoding, suitable for passing between services.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Serializing into a Vec cannot fail
+        self.try_to_vec().unwrap()
+    }
+
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, VerifyError> {
+        let signed = Self::deserialize(&mut bytes).map_err(|_| VerifyError::Malformed)?;
+        if !bytes.is_empty() {
+            return Err(VerifyError::Malformed);
+        }
+        Ok(signed)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    Malformed,
+    UnsupportedVersion,
+    WrongProgram,
+    SoulKeyAddressMismatch,
+    AccountAddressMismatch,
+    UntrustedAttestor,
+    InvalidSignature,
+    Stale,
+    InvalidAccountData,
+    SnapshotMismatch,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            VerifyError::Malformed => "attestation bytes are malformed",
+            VerifyError::UnsupportedVersion => "unsupported attestation version",
+            VerifyError::WrongProgram => "attestation was issued for a different program",
+            VerifyError::SoulKeyAddressMismatch => "attested account is not the owner's SoulKey PDA",
+            VerifyError::AccountAddressMismatch => "account data was fetched from a different address than the attested SoulKey",
+            VerifyError::UntrustedAttestor => "attestation was not signed by a trusted attestor",
+            VerifyError::InvalidSignature => "attestation signature is invalid",
+            VerifyError::Stale => "attestation is older than the allowed age",
+            VerifyError::InvalidAccountData => "account data is not a SoulKey owned by the program",
+            VerifyError::SnapshotMismatch => "attestation does not match the SoulKey account",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+pub struct Verifier {
+    pub program_id: Pubkey,
+    pub trusted_attestors: Vec<Pubkey>, // Usually just `ProtocolState::attestor`
+    pub max_age_slots: u64,
+}
+
+impl Verifier {
+    pub fn new(trusted_attestors: Vec<Pubkey>, max_age_slots: u64) -> Self {
+        Self {
+            program_id: c_protocol::ID,
+            trusted_attestors,
+            max_age_slots,
+        }
+    }
+
+    /// Checks the signature and the snapshot's provenance, returning the attested
+    /// data only if everything holds at `current_slot`.
+    pub fn verify<'a>(
+        &self,
+        signed: &'a SignedAttestation,
+        current_slot: u64,
+    ) -> Result<&'a ReputationAttestation, VerifyError> {
+        let attestation = &signed.attestation;
+        if attestation.version != ATTESTATION_VERSION {
+            return Err(VerifyError::UnsupportedVersion);
+        }
+        if attestation.program_id != self.program_id {
+            return Err(VerifyError::WrongProgram);
+        }
+        if attestation.soul_key != soul_key_address(&attestation.owner, &self.program_id) {
+            return Err(VerifyError::SoulKeyAddressMismatch);
+        }
+        if !self.trusted_attestors.contains(&signed.attestor) {
+            return Err(VerifyError::UntrustedAttestor);
+        }
+
+        let public_key =
+            PublicKey::from_bytes(signed.attestor.as_ref()).map_err(|_| VerifyError::InvalidSignature)?;
+        let signature =
+            Signature::from_bytes(&signed.signature).map_err(|_| VerifyError::InvalidSignature)?;
+        public_key
+            .verify_strict(&attestation.signing_message(), &signature)
+            .map_err(|_| VerifyError::InvalidSignature)?;
+
+        // Snapshots from the future are treated as fresh; the signature already vouches for them
+        if current_slot.saturating_sub(attestation.slot) > self.max_age_slots {
+            return Err(VerifyError::Stale);
+        }
+        Ok(attestation)
+    }
+
+    /// Compares a snapshot with raw SoulKey account data from a trusted RPC node.
+    /// `account_address` is where the data was fetched from and `account_owner`
+    /// the program that owns it.
+    pub fn verify_against_account(
+        &self,
+        attestation: &ReputationAttestation,
+        account_address: &Pubkey,
+        account_owner: &Pubkey,
+        account_data: &[u8],
+    ) -> Result<(), VerifyError> {
+        if account_address != &attestation.soul_key {
+            return Err(VerifyError::AccountAddressMismatch);
+        }
+        if account_owner != &self.program_id {
+            return Err(VerifyError::InvalidAccountData);
+        }
+        let soul_key = SoulKey::try_deserialize(&mut &account_data[..])
+            .map_err(|_| VerifyError::InvalidAccountData)?;
+        if !attestation.matches(&soul_key) {
+            return Err(VerifyError::SnapshotMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// The SoulKey PDA for `owner`, mirroring the program's `[b"soul_key", owner]` seeds.
+pub fn soul_key_address(owner: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"soul_key", owner.as_ref()], program_id).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Clock;
+    use anchor_lang::AccountSerialize;
+    use ed25519_dalek::{Keypair, SecretKey, Signer};
+
+    const SLOT: u64 = 1_000;
+
+    fn keypair(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+
+    fn pubkey(keypair: &Keypair) -> Pubkey {
+        Pubkey::new_from_array(keypair.public.to_bytes())
+    }
+
+    fn sign(attestation: ReputationAttestation, attestor: &Keypair) -> SignedAttestation {
+        let signature = attestor.sign(&attestation.signing_message()).to_bytes();
+        SignedAttestation {
+            attestation,
+            attestor: pubkey(attestor),
+            signature,
+        }
+    }
+
+    struct Fixture {
+        attestor: Keypair,
+        soul_key: SoulKey,
+        address: Pubkey,
+        signed: SignedAttestation,
+    }
+
+    fn fixture() -> Fixture {
+        let attestor = keypair(1);
+        let owner = Pubkey::new_from_array([2; 32]);
+        let clock = Clock {
+            slot: SLOT,
+            unix_timestamp: 1_700_000_000,
+            ..Clock::default()
+        };
+        let mut soul_key = SoulKey::new(owner, &clock);
+        soul_key.trust_score = 4_200;
+        soul_key.total_work_completed = 17;
+        let address = soul_key_address(&owner, &c_protocol::ID);
+        let signed = sign(ReputationAttestation::snapshot(address, &soul_key, &clock), &attestor);
+        Fixture {
+            attestor,
+            soul_key,
+            address,
+            signed,
+        }
+    }
+
+    fn verifier(fixture: &Fixture) -> Verifier {
+        Verifier::new(vec![pubkey(&fixture.attestor)], 100)
+    }
+
+    fn account_data(soul_key: &SoulKey) -> Vec<u8> {
+        let mut data = Vec::new();
+        soul_key.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn valid_attestation_verifies_end_to_end() {
+        let fixture = fixture();
+        let verifier = verifier(&fixture);
+
+        let decoded = SignedAttestation::from_bytes(&fixture.signed.to_bytes()).unwrap();
+        let attestation = verifier.verify(&decoded, SLOT + 100).unwrap();
+        verifier
+            .verify_against_account(
+                attestation,
+                &fixture.address,
+                &c_protocol::ID,
+                &account_data(&fixture.soul_key),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn tampered_snapshot_or_signature_is_rejected() {
+        let fixture = fixture();
+        let verifier = verifier(&fixture);
+
+        let mut tampered = fixture.signed.clone();
+        tampered.attestation.trust_score += 1;
+        assert_eq!(verifier.verify(&tampered, SLOT), Err(VerifyError::InvalidSignature));
+
+        let mut tampered = fixture.signed.clone();
+        tampered.signature[0] ^= 1;
+        assert_eq!(verifier.verify(&tampered, SLOT), Err(VerifyError::InvalidSignature));
+
+        // Valid signature from a key the partner does not trust
+        let forged = sign(fixture.signed.attestation.clone(), &keypair(3));
+        assert_eq!(verifier.verify(&forged, SLOT), Err(VerifyError::UntrustedAttestor));
+    }
+
+    #[test]
+    fn attestation_must_name_the_owners_soul_key_pda() {
+        let fixture = fixture();
+        let verifier = verifier(&fixture);
+
+        // Correctly signed, but for an address that is not the owner's PDA
+        let mut attestation = fixture.signed.attestation.clone();
+        attestation.soul_key = Pubkey::new_from_array([9; 32]);
+        let signed = sign(attestation, &fixture.attestor);
+        assert_eq!(verifier.verify(&signed, SLOT), Err(VerifyError::SoulKeyAddressMismatch));
+
+        let mut attestation = fixture.signed.attestation.clone();
+        attestation.program_id = Pubkey::new_from_array([9; 32]);
+        let signed = sign(attestation, &fixture.attestor);
+        assert_eq!(verifier.verify(&signed, SLOT), Err(VerifyError::WrongProgram));
+    }
+
+    #[test]
+    fn stale_attestation_is_rejected() {
+        let fixture = fixture();
+        assert_eq!(
+            verifier(&fixture).verify(&fixture.signed, SLOT + 101),
+            Err(VerifyError::Stale)
+        );
+    }
+
+    #[test]
+    fn snapshot_reports_trust_reverted_since_the_last_write() {
+        let fixture = fixture();
+        let verifier = verifier(&fixture);
+
+        // Ten trust epochs later, with no write to the account in between
+        let later = Clock {
+            slot: SLOT + 50,
+            unix_timestamp: fixture.signed.attestation.timestamp + 10 * c_protocol::TRUST_EPOCH_SECONDS,
+            ..Clock::default()
+        };
+        let attestation = ReputationAttestation::snapshot(fixture.address, &fixture.soul_key, &later);
+        assert!(attestation.trust_score < fixture.soul_key.trust_score);
+        verifier
+            .verify_against_account(
+                &attestation,
+                &fixture.address,
+                &c_protocol::ID,
+                &account_data(&fixture.soul_key),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn account_check_rejects_wrong_address_owner_or_contents() {
+        let fixture = fixture();
+        let verifier = verifier(&fixture);
+        let attestation = &fixture.signed.attestation;
+        let data = account_data(&fixture.soul_key);
+
+        // Another SoulKey's data cannot stand in for the attested one
+        let other_address = soul_key_address(&Pubkey::new_from_array([4; 32]), &c_protocol::ID);
+        assert_eq!(
+            verifier.verify_against_account(attestation, &other_address, &c_protocol::ID, &data),
+            Err(VerifyError::AccountAddressMismatch)
+        );
+        let other_program = Pubkey::new_from_array([5; 32]);
+        assert_eq!(
+            verifier.verify_against_account(attestation, &fixture.address, &other_program, &data),
+            Err(VerifyError::InvalidAccountData)
+        );
+        assert_eq!(
+            verifier.verify_against_account(attestation, &fixture.address, &c_protocol::ID, &data[..8]),
+            Err(VerifyError::InvalidAccountData)
+        );
+
+        let mut changed = fixture.soul_key.clone();
+        changed.trust_score -= 100;
+        assert_eq!(
+            verifier.verify_against_account(
+                attestation,
+                &fixture.address,
+                &c_protocol::ID,
+                &account_data(&changed)
+            ),
+            Err(VerifyError::SnapshotMismatch)
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! Deterministic SVG avatars for SoulKeys.
+//!
+//! Every visual element is derived from the SoulKey traits with integer-only
+//! math and a fixed hash, so the same SoulKey renders byte-identical SVG on any
+//! platform: the metadata server and tests can compare output directly.
+
+use std::fmt::Write;
+
+pub const AVATAR_SIZE: u32 = 256;
+const CENTER: i64 = (AVATAR_SIZE / 2) as i64;
+const MAX_TRUST_RINGS: u64 = 5;
+const BADGE_SLOTS: u32 = 10;
+
+/// Mirrors `c_protocol::WorkType`, in declaration order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Specialty {
+    OnboardUser,
+    CreateContent,
+    WriteCode,
+    ReferClient,
+    CloseDeal,
+    CommunityManagement,
+    BugReport,
+    Documentation,
+    Marketing,
+    UserSupport,
+}
+
+impl Specialty {
+    fn hue(self) -> u32 {
+        match self {
+            Specialty::OnboardUser => 140,
+            Specialty::CreateContent => 300,
+            Specialty::WriteCode => 200,
+            Specialty::ReferClient => 45,
+            Specialty::CloseDeal => 0,
+            Specialty::CommunityManagement => 170,
+            Specialty::BugReport => 20,
+            Specialty::Documentation => 230,
+            Specialty::Marketing => 330,
+            Specialty::UserSupport => 100,
+        }
+    }
+}
+
+/// The SoulKey fields that shape an avatar.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AvatarTraits {
+    pub owner: [u8; 32],
+    pub level: u8,
+    pub specialty: Specialty,
+    pub trust_score: u64,
+    pub badges: u16, // Bit n set = BadgeType variant n earned
+}
+
+/// Stable 64-bit seed for the traits (FNV-1a), unlike `DefaultHasher` which may
+/// change between Rust releases. The chain stores it as the SoulKey's
+/// `current_avatar_hash`, so it names the image `render_svg` draws.
+pub fn avatar_seed(traits: &AvatarTraits) -> u64 {
+    let mut hash = Fnv1a::new();
+    hash.write(&traits.owner);
+    hash.write(&[traits.level, traits.specialty as u8]);
+    hash.write(&traits.trust_score.to_le_bytes());
+    hash.write(&traits.badges.to_le_bytes());
+    hash.finish()
+}
+
+/// Renders the avatar as a standalone SVG document.
+pub fn render_svg(traits: &AvatarTraits) -> String {
+    let owner_seed = {
+        let mut hash = Fnv1a::new();
+        hash.write(&traits.owner);
+        hash.finish()
+    };
+    let level = traits.level.clamp(1, 50) as i64;
+
+    // Background colors identify the owner and never change as the soul evolves
+    let background_hue = (owner_seed % 360) as u32;
+    let background_accent = (background_hue + 40 + ((owner_seed >> 16) % 80) as u32) % 360;
+    let core_hue = traits.specialty.hue();
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#,
+        size = AVATAR_SIZE
+    );
+    let _ = write!(
+        svg,
+        r#"<defs><linearGradient id="bg" x1="0" y1="0" x2="1" y2="1"><stop offset="0" stop-color="hsl({background_hue},45%,18%)"/><stop offset="1" stop-color="hsl({background_accent},55%,30%)"/></linearGradient></defs>"#
+    );
+    let _ = write!(
+        svg,
+        r#"<rect width="{size}" height="{size}" fill="url(#bg)"/>"#,
+        size = AVATAR_SIZE
+    );
+
+    // Trust rings: one per 1000 trust, up to five
+    let core_radius = 40 + level;
+    let rings = (traits.trust_score / 1000).min(MAX_TRUST_RINGS) as i64;
+    for ring in 1..=rings {
+        let _ = write!(
+            svg,
+            r#"<circle cx="{CENTER}" cy="{CENTER}" r="{}" fill="none" stroke="hsl({core_hue},80%,70%)" stroke-opacity="0.{}" stroke-width="2"/>"#,
+            core_radius + 6 * ring,
+            10 - ring
+        );
+    }
+
+    // Core shape evolves with level
+    let core_fill = format!("hsl({core_hue},70%,{}%)", 35 + level / 2);
+    match level {
+        1..=9 => {
+            let _ = write!(
+                svg,
+                r#"<circle cx="{CENTER}" cy="{CENTER}" r="{core_radius}" fill="{core_fill}"/>"#
+            );
+        }
+        10..=19 => write_polygon(&mut svg, &polygon_points(6, core_radius, core_radius), &core_fill),
+        20..=34 => write_polygon(&mut svg, &polygon_points(8, core_radius, core_radius), &core_fill),
+        _ => write_polygon(&mut svg, &polygon_points(12, core_radius, core_radius * 3 / 5), &core_fill),
+    }
+
+    // Inner glyph offset by the owner seed so souls at the same level differ
+    let glyph_angle = ((owner_seed >> 32) % 24) as u32 * 15;
+    let (glyph_x, glyph_y) = point_on_circle(glyph_angle, core_radius / 4);
+    let _ = write!(
+        svg,
+        r#"<circle cx="{glyph_x}" cy="{glyph_y}" r="{}" fill="hsl({background_accent},90%,85%)"/>"#,
+        core_radius / 4
+    );
+
+    // Badge pips around the edge, one slot per badge type, offset to keep the
+    // top and bottom clear for the level label
+    for slot in 0..BADGE_SLOTS {
+        if traits.badges & (1 << slot) == 0 {
+            continue;
+        }
+        let (x, y) = point_on_circle(slot * 30 + 15, CENTER - 14);
+        let _ = write!(
+            svg,
+            r#"<circle cx="{x}" cy="{y}" r="7" fill="hsl({},85%,60%)" stroke="white" stroke-width="1"/>"#,
+            (slot * 36) % 360
+        );
+    }
+
+    let _ = write!(
+        svg,
+        r#"<text x="{CENTER}" y="{}" fill="white" font-family="monospace" font-size="14" text-anchor="middle">LV {level}</text>"#,
+        AVATAR_SIZE - 8
+    );
+    svg.push_str("</svg>");
+    svg
+}
+
+fn write_polygon(svg: &mut String, points: &[(i64, i64)], fill: &str) {
+    let points = points
+        .iter()
+        .map(|(x, y)| format!("{x},{y}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let _ = write!(svg, r#"<polygon points="{points}" fill="{fill}"/>"#);
+}
+
+/// Regular polygon (or star when the radii differ) with `sides` vertices; `sides`
+/// must divide 24 so vertices land on the 15 degree lookup table.
+fn polygon_points(sides: u32, outer_radius: i64, inner_radius: i64) -> Vec<(i64, i64)> {
+    let step = 360 / sides;
+    (0..sides)
+        .map(|i| {
+            let radius = if i % 2 == 0 { outer_radius } else { inner_radius };
+            point_on_circle(i * step, radius)
+        })
+        .collect()
+}
+
+/// Integer point on a circle around the center; `degrees` must be a multiple of 15.
+fn point_on_circle(degrees: u32, radius: i64) -> (i64, i64) {
+    let (cos, sin) = unit_vector(degrees);
+    (CENTER + radius * cos / 1000, CENTER - radius * sin / 1000)
+}
+
+/// cos/sin scaled by 1000 for multiples of 15 degrees.
+fn unit_vector(degrees: u32) -> (i64, i64) {
+    const QUADRANT: [i64; 7] = [1000, 966, 866, 707, 500, 259, 0];
+    let step = (degrees % 360 / 15) as usize;
+    let quadrant_step = step % 6;
+    let (cos, sin) = (QUADRANT[quadrant_step], QUADRANT[6 - quadrant_step]);
+    match step / 6 {
+        0 => (cos, sin),
+        1 => (-sin, cos),
+        2 => (-cos, -sin),
+        _ => (sin, -cos),
+    }
+}
+
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(feature = "c-protocol")]
+mod soul_key {
+    use super::{AvatarTraits, Specialty};
+    use c_protocol::{SoulKey, WorkType};
+
+    impl From<WorkType> for Specialty {
+        fn from(work_type: WorkType) -> Self {
+            match work_type {
+                WorkType::OnboardUser => Specialty::OnboardUser,
+                WorkType::CreateContent => Specialty::CreateContent,
+                WorkType::WriteCode => Specialty::WriteCode,
+                WorkType::ReferClient => Specialty::ReferClient,
+                WorkType::CloseDeal => Specialty::CloseDeal,
+                WorkType::CommunityManagement => Specialty::CommunityManagement,
+                WorkType::BugReport => Specialty::BugReport,
+                WorkType::Documentation => Specialty::Documentation,
+                WorkType::Marketing => Specialty::Marketing,
+                WorkType::UserSupport => Specialty::UserSupport,
+            }
+        }
+    }
+
+    impl From<&SoulKey> for AvatarTraits {
+        fn from(soul_key: &SoulKey) -> Self {
+            AvatarTraits {
+                owner: soul_key.owner.to_bytes(),
+                level: soul_key.evolution_level,
+                specialty: soul_key.dominant_work_type.into(),
+                trust_score: soul_key.trust_score,
+                badges: soul_key.badge_mask,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::avatar_seed;
+        use c_protocol::BadgeType;
+
+        #[test]
+        fn chain_stores_the_renderer_seed() {
+            let clock = Default::default();
+            let mut soul_key = SoulKey::new([7; 32].into(), &clock);
+            assert_eq!(avatar_seed(&AvatarTraits::from(&soul_key)), soul_key.current_avatar_hash);
+
+            soul_key.evolution_level = 12;
+            soul_key.dominant_work_type = WorkType::WriteCode;
+            soul_key.trust_score = 2_500;
+            soul_key.badge_mask = BadgeType::Mentor.bit();
+            soul_key.rotate_owner([8; 32].into(), &clock);
+            assert_eq!(avatar_seed(&AvatarTraits::from(&soul_key)), soul_key.current_avatar_hash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn traits(level: u8, specialty: Specialty, trust_score: u64, badges: u16) -> AvatarTraits {
+        AvatarTraits {
+            owner: [7; 32],
+            level,
+            specialty,
+            trust_score,
+            badges,
+        }
+    }
+
+    // One fixture per core shape, with rings and badges varied alongside
+    fn golden_fixtures() -> [(&'static str, AvatarTraits); 4] {
+        [
+            ("newcomer", traits(1, Specialty::OnboardUser, 0, 0)),
+            ("hexagon", traits(12, Specialty::WriteCode, 2_500, 0b0000_0000_0101)),
+            ("octagon", traits(27, Specialty::BugReport, 4_200, 0b0011_0011_0011)),
+            ("star", traits(50, Specialty::CloseDeal, 9_999, 0b0011_1111_1111)),
+        ]
+    }
+
+    /// Compares against tests/golden/<name>.svg; run with SOUL_AVATAR_BLESS=1
+    /// to rewrite the files after an intended rendering change.
+    fn assert_golden(name: &str, svg: &str) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(format!("{name}.svg"));
+        if std::env::var_os("SOUL_AVATAR_BLESS").is_some() {
+            std::fs::write(&path, svg).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("missing golden {}", path.display()));
+        assert_eq!(svg, expected, "{name} no longer matches its golden SVG");
+    }
+
+    #[test]
+    fn same_traits_render_identical_svg() {
+        for (_, fixture) in golden_fixtures() {
+            let copy = fixture.clone();
+            assert_eq!(render_svg(&fixture), render_svg(&copy));
+            assert_eq!(avatar_seed(&fixture), avatar_seed(&copy));
+        }
+    }
+
+    #[test]
+    fn every_trait_changes_the_seed() {
+        let base = traits(12, Specialty::WriteCode, 2_500, 0b101);
+        let variants = [
+            AvatarTraits { owner: [8; 32], ..base.clone() },
+            AvatarTraits { level: 13, ..base.clone() },
+            AvatarTraits { specialty: Specialty::Marketing, ..base.clone() },
+            // Crosses into a third trust ring
+            AvatarTraits { trust_score: 3_000, ..base.clone() },
+            AvatarTraits { badges: 0b111, ..base.clone() },
+        ];
+        for variant in variants {
+            assert_ne!(avatar_seed(&base), avatar_seed(&variant), "{variant:?}");
+            assert_ne!(render_svg(&base), render_svg(&variant), "{variant:?}");
+        }
+    }
+
+    #[test]
+    fn seed_is_pinned() {
+        // A hasher change fails here instead of silently re-rendering every avatar
+        assert_eq!(
+            avatar_seed(&traits(1, Specialty::OnboardUser, 0, 0)),
+            13_289_430_591_265_173_892
+        );
+    }
+
+    #[test]
+    fn renders_match_golden_files() {
+        for (name, fixture) in golden_fixtures() {
+            assert_golden(name, &render_svg(&fixture));
+        }
+    }
+
+    #[test]
+    fn unit_vectors_stay_on_the_circle() {
+        for degrees in (0..360).step_by(15) {
+            let (cos, sin) = unit_vector(degrees);
+            let length_squared = cos * cos + sin * sin;
+            assert!((length_squared - 1_000_000).abs() < 1_000, "{degrees} degrees");
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+
+use crate::{CoinFlipError, GameKind, GameState, LP_WITHDRAWAL_DELAY, SECONDS_PER_DAY};
+
+pub const MAX_HOUSE_EDGE_BPS: u16 = 1_000; // 10%
+// Longer than an LP withdrawal, so LPs can exit before new risk terms apply
+pub const LP_CONFIG_TIMELOCK: i64 = LP_WITHDRAWAL_DELAY + SECONDS_PER_DAY;
+
+/// Fields `update_config` changes; `None` leaves the current value in place.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigUpdate {
+    pub house_edge: Option<u16>,
+    pub min_bet: Option<u64>,
+    pub max_bet: Option<u64>,
+    pub house_signer: Option<Pubkey>,
+    pub max_payout_bps: Option<u16>,
+    pub max_slot_exposure: Option<u64>,
+    pub coin_flip_max_win: Option<u64>,
+    pub dice_max_win: Option<u64>,
+}
+
+/// Risk terms queued by `update_config` while LP shares are live.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct PendingConfig {
+    pub house_edge: Option<u16>,
+    pub house_signer: Option<Pubkey>,
+    pub max_payout_bps: Option<u16>,
+    pub effective_at: i64,
+}
+
+impl GameState {
+    /// Applies an update right away, except that once LP shares exist the edge,
+    /// house signer and payout cap are queued for `LP_CONFIG_TIMELOCK` instead.
+    /// A new queued change replaces the previous one and restarts the timelock.
+    pub fn update_config(&mut self, update: &ConfigUpdate, now: i64) -> Result<Option<PendingConfig>> {
+        if self.lp_mint.is_none() {
+            self.apply_config(update)?;
+            return Ok(None);
+        }
+
+        let mut immediate = update.clone();
+        let queued = PendingConfig {
+            house_edge: immediate.house_edge.take(),
+            house_signer: immediate.house_signer.take(),
+            max_payout_bps: immediate.max_payout_bps.take(),
+            effective_at: now + LP_CONFIG_TIMELOCK,
+        };
+        let has_queued =
+            queued.house_edge.is_some() || queued.house_signer.is_some() || queued.max_payout_bps.is_some();
+
+        // Validate the queued terms now so they cannot fail once they come due
+        if has_queued {
+            self.clone().apply_config(update)?;
+        }
+        self.apply_config(&immediate)?;
+        if has_queued {
+            self.pending_config = Some(queued);
+            return Ok(Some(queued));
+        }
+        Ok(None)
+    }
+
+    /// Applies the queued risk terms once their timelock has passed.
+    pub fn apply_pending_config(&mut self, now: i64) -> Result<()> {
+        let pending = self.pending_config.ok_or(CoinFlipError::NoPendingConfig)?;
+        require!(now >= pending.effective_at, CoinFlipError::ConfigTimelockActive);
+
+        self.apply_config(&ConfigUpdate {
+            house_edge: pending.house_edge,
+            house_signer: pending.house_signer,
+            max_payout_bps: pending.max_payout_bps,
+            ..Default::default()
+        })?;
+        self.pending_config = None;
+        Ok(())
+    }
+
+    /// The current or queued house signer, which knows committed server seeds.
+    pub fn is_house_signer(&self, key: &Pubkey) -> bool {
+        *key == self.house_signer
+            || self
+                .pending_config
+                .and_then(|pending| pending.house_signer)
+                .is_some_and(|signer| signer == *key)
+    }
+
+    /// Applies an update and validates the resulting config as a whole. Open
+    /// bets keep the payout fixed at placement, so changes only affect new bets.
+    pub fn apply_config(&mut self, update: &ConfigUpdate) -> Result<()> {
+        if let Some(house_edge) = update.house_edge {
+            self.house_edge = house_edge;
+        }
+        if let Some(min_bet) = update.min_bet {
+            self.min_bet = min_bet;
+        }
+        if let Some(max_bet) = update.max_bet {
+            self.max_bet = max_bet;
+        }
+        if let Some(house_signer) = update.house_signer {
+            self.house_signer = house_signer;
+        }
+        if let Some(max_payout_bps) = update.max_payout_bps {
+            self.max_payout_bps = max_payout_bps;
+        }
+        if let Some(max_slot_exposure) = update.max_slot_exposure {
+            self.max_slot_exposure = max_slot_exposure;
+        }
+        if let Some(max_win) = update.coin_flip_max_win {
+            self.max_win[GameKind::CoinFlip.index()] = max_win;
+        }
+        if let Some(max_win) = update.dice_max_win {
+            self.max_win[GameKind::Dice.index()] = max_win;
+        }
+
+        require!(self.house_edge <= MAX_HOUSE_EDGE_BPS, CoinFlipError::InvalidConfig);
+        require!(self.min_bet > 0 && self.min_bet <= self.max_bet, CoinFlipError::InvalidConfig);
+        require!(
+            self.max_payout_bps > 0 && self.max_payout_bps <= 10_000,
+            CoinFlipError::InvalidConfig
+        );
+        require!(self.max_slot_exposure > 0, CoinFlipError::InvalidConfig);
+        require!(self.max_win.iter().all(|&max_win| max_win > 0), CoinFlipError::InvalidConfig);
+        require!(self.house_signer != Pubkey::default(), CoinFlipError::InvalidConfig);
+        Ok(())
+    }
+
+    /// Vault balance free to leave: everything not reserved for open bets.
+    pub fn withdrawable(&self, vault_amount: u64) -> u64 {
+        vault_amount.saturating_sub(self.pending_payouts)
+    }
+
+    /// The most the vault can hold once every open bet settles: the balance
+    /// plus every escrowed stake, as if all of them lost.
+    pub fn deposit_assets(&self, vault_amount: u64) -> u64 {
+        vault_amount.saturating_add(self.open_stakes)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{DEFAULT_MAX_PAYOUT_BPS, DEFAULT_MAX_SLOT_EXPOSURE, GAME_KIND_COUNT};
+
+    pub(crate) fn game_state() -> GameState {
+        GameState {
+            house_edge: 200,
+            min_bet: 1_000_000,
+            max_bet: 1_000_000_000,
+            total_games: 0,
+            house_balance: 0,
+            house_signer: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            native_sol: false,
+            house_bump: 255,
+            vault_bump: 255,
+            max_payout_bps: DEFAULT_MAX_PAYOUT_BPS,
+            max_slot_exposure: DEFAULT_MAX_SLOT_EXPOSURE,
+            exposure_slot: 0,
+            slot_exposure: 0,
+            pending_payouts: 0,
+            open_stakes: 0,
+            authority: Pubkey::new_unique(),
+            paused: false,
+            max_win: [u64::MAX; GAME_KIND_COUNT],
+            lp_mint: None,
+            lp_mint_bump: 0,
+            pending_config: None,
+        }
+    }
+
+    #[test]
+    fn risk_terms_wait_out_the_timelock_once_lp_shares_exist() {
+        let signer = Pubkey::new_unique();
+        let update = ConfigUpdate {
+            house_edge: Some(500),
+            house_signer: Some(signer),
+            max_bet: Some(2_000_000_000),
+            ..Default::default()
+        };
+
+        // Without LPs everything applies at once
+        let mut state = game_state();
+        assert!(state.update_config(&update, 0).unwrap().is_none());
+        assert_eq!((state.house_edge, state.house_signer), (500, signer));
+
+        let mut state = game_state();
+        state.lp_mint = Some(Pubkey::new_unique());
+        let queued = state.update_config(&update, 100).unwrap().unwrap();
+        assert_eq!(queued.effective_at, 100 + LP_CONFIG_TIMELOCK);
+        assert_eq!(state.max_bet, 2_000_000_000);
+        assert_eq!(state.house_edge, 200);
+        assert!(state.is_house_signer(&signer));
+
+        assert!(state.apply_pending_config(99 + LP_CONFIG_TIMELOCK).is_err());
+        state.apply_pending_config(100 + LP_CONFIG_TIMELOCK).unwrap();
+        assert_eq!((state.house_edge, state.house_signer), (500, signer));
+        assert!(state.apply_pending_config(100 + LP_CONFIG_TIMELOCK).is_err());
+    }
+
+    #[test]
+    fn invalid_queued_terms_are_rejected_up_front() {
+        let mut state = game_state();
+        state.lp_mint = Some(Pubkey::new_unique());
+        let update = ConfigUpdate {
+            house_edge: Some(MAX_HOUSE_EDGE_BPS + 1),
+            max_bet: Some(2_000_000_000),
+            ..Default::default()
+        };
+        assert!(state.update_config(&update, 0).is_err());
+        assert_eq!(state.max_bet, 1_000_000_000);
+        assert!(state.pending_config.is_none());
+    }
+}
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    CoinFlipError, Draw, GameKind, COIN_FLIP_WIN_CHANCE_BPS, REVEAL_TIMEOUT_SLOTS, ROLL_RANGE,
+    SETTLE_DELAY_SLOTS,
+};
+
+pub const BET_ESCROW_SEED: &[u8] = b"bet_escrow";
+
+/// A wager between placement and settlement. The stake sits in an escrow token
+/// account owned by the bet until it settles, either from SlotHashes or from
+/// the house revealing its committed seed; both accounts then close to the player.
+#[account]
+#[derive(InitSpace)]
+pub struct Bet {
+    pub player: Pubkey,
+    pub game_state: Pubkey,
+    pub bet_id: u64,
+    pub bet_amount: u64,
+    pub payout: u64, // Paid on a win, fixed at placement
+    pub kind: GameKind,
+    pub choice: bool, // Coin flip only: true = heads, false = tails
+    pub roll_under: u16, // Dice only: wins when the roll is below this
+    pub commit_slot: u64,
+    pub house_seed: Option<Pubkey>, // Set for commit-reveal bets; None settles from SlotHashes
+    pub client_seed: [u8; 32],
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
+
+impl Bet {
+    /// First slot whose block hash can decide the bet.
+    pub fn target_slot(&self) -> u64 {
+        self.commit_slot + SETTLE_DELAY_SLOTS
+    }
+
+    /// A commit-reveal bet the house failed to reveal in time pays out as a win.
+    pub fn reveal_timed_out(&self, clock: &Clock) -> bool {
+        clock.slot > self.commit_slot + REVEAL_TIMEOUT_SLOTS
+    }
+
+    pub fn win_chance_bps(&self) -> u16 {
+        match self.kind {
+            GameKind::CoinFlip => COIN_FLIP_WIN_CHANCE_BPS,
+            GameKind::Dice => self.roll_under,
+        }
+    }
+
+    pub fn player_won(&self, draw: &Draw) -> bool {
+        match self.kind {
+            GameKind::CoinFlip => draw.flip_result == self.choice,
+            GameKind::Dice => draw.roll < self.roll_under,
+        }
+    }
+
+    /// What the vault owes on a win beyond the escrowed stake; reserved until settlement.
+    pub fn house_liability(&self) -> u64 {
+        self.payout.saturating_sub(self.bet_amount)
+    }
+}
+
+/// Payout on a win: the stake times `(10000 - house_edge) / win_chance_bps`.
+/// Every game then returns `10000 - house_edge` bps of each stake on average.
+pub fn potential_payout(bet_amount: u64, house_edge: u16, win_chance_bps: u16) -> Result<u64> {
+    require!(
+        win_chance_bps > 0 && win_chance_bps <= ROLL_RANGE,
+        CoinFlipError::InvalidRollTarget
+    );
+    let payout = (bet_amount as u128)
+        .checked_mul(10_000u128.checked_sub(house_edge as u128).ok_or(CoinFlipError::MathOverflow)?)
+        .ok_or(CoinFlipError::MathOverflow)?
+        / win_chance_bps as u128;
+    u64::try_from(payout).map_err(|_| error!(CoinFlipError::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commit_reveal_entropy, MAX_ROLL_UNDER, MIN_ROLL_UNDER};
+
+    // Expected house profit at the given win chance, in bps of the stake
+    fn expected_edge_bps(bet_amount: u64, house_edge: u16, win_chance_bps: u16) -> f64 {
+        let payout = potential_payout(bet_amount, house_edge, win_chance_bps).unwrap() as f64;
+        let win_chance = win_chance_bps as f64 / 10_000.0;
+        (bet_amount as f64 - payout * win_chance) / bet_amount as f64 * 10_000.0
+    }
+
+    #[test]
+    fn expected_edge_matches_house_edge() {
+        for win_chance_bps in [MIN_ROLL_UNDER, 333, COIN_FLIP_WIN_CHANCE_BPS, 7_777, MAX_ROLL_UNDER] {
+            for house_edge in [0u16, 1, 50, 200, 333, 1000, 5000] {
+                for bet_amount in [1_000_000u64, 1_234_567, 999_999_999, 1_000_000_000, u64::MAX / 200] {
+                    let edge = expected_edge_bps(bet_amount, house_edge, win_chance_bps);
+                    // Flooring the payout can only favour the house, by under one unit
+                    let rounding_bps = 10_000.0 / bet_amount as f64;
+                    assert!(
+                        edge >= house_edge as f64 - 1e-6 && edge <= house_edge as f64 + rounding_bps + 1e-6,
+                        "edge {edge} for house_edge {house_edge}, chance {win_chance_bps}, bet {bet_amount}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn realized_edge_converges_to_house_edge() {
+        let house_edge = 200u16;
+        let bet_amount = 1_000_000u64;
+        let payout = potential_payout(bet_amount, house_edge, COIN_FLIP_WIN_CHANCE_BPS).unwrap();
+        let rounds = 200_000u64;
+
+        let mut house_profit: i128 = 0;
+        for nonce in 0..rounds {
+            let entropy = commit_reveal_entropy(&[7; 32], &[(nonce % 251) as u8; 32], nonce);
+            let player_won = Draw::from_entropy(&entropy).flip_result;
+            house_profit += bet_amount as i128;
+            if player_won {
+                house_profit -= payout as i128;
+            }
+        }
+
+        let realized_bps = house_profit as f64 / (rounds * bet_amount) as f64 * 10_000.0;
+        // Three standard deviations of the per-bet edge over 200k flips is ~67 bps
+        assert!((realized_bps - house_edge as f64).abs() < 70.0, "realized {realized_bps} bps");
+    }
+
+    #[test]
+    fn dice_realized_edge_converges_to_house_edge() {
+        let house_edge = 200u16;
+        let bet_amount = 1_000_000u64;
+        let roll_under = 2_500u16;
+        let payout = potential_payout(bet_amount, house_edge, roll_under).unwrap();
+        let rounds = 200_000u64;
+
+        let mut house_profit: i128 = 0;
+        for nonce in 0..rounds {
+            let entropy = commit_reveal_entropy(&[9; 32], &[(nonce % 241) as u8; 32], nonce);
+            house_profit += bet_amount as i128;
+            if Draw::from_entropy(&entropy).roll < roll_under {
+                house_profit -= payout as i128;
+            }
+        }
+
+        let realized_bps = house_profit as f64 / (rounds * bet_amount) as f64 * 10_000.0;
+        // At a 25% win chance three standard deviations over 200k rolls is ~116 bps
+        assert!((realized_bps - house_edge as f64).abs() < 120.0, "realized {realized_bps} bps");
+    }
+
+    #[test]
+    fn payout_never_overflows_silently() {
+        assert!(potential_payout(u64::MAX, 0, COIN_FLIP_WIN_CHANCE_BPS).is_err());
+        assert_eq!(potential_payout(u64::MAX, 5000, COIN_FLIP_WIN_CHANCE_BPS).unwrap(), u64::MAX);
+        assert!(potential_payout(u64::MAX / 50, 0, MIN_ROLL_UNDER).is_err());
+        assert!(potential_payout(1, 10_001, COIN_FLIP_WIN_CHANCE_BPS).is_err());
+        assert!(potential_payout(1, 200, 0).is_err());
+        assert_eq!(potential_payout(0, 200, COIN_FLIP_WIN_CHANCE_BPS).unwrap(), 0);
+    }
+}
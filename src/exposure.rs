@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::{Bet, CoinFlipError, GameState};
+
+pub const DEFAULT_MAX_PAYOUT_BPS: u16 = 200; // One payout may take 2% of free liquidity
+pub const DEFAULT_MAX_SLOT_EXPOSURE: u64 = 20_000_000_000; // Ten max bets paying out in one slot
+
+impl GameState {
+    /// Reserves what a bet could take from the vault beyond its escrowed stake
+    /// and counts the stake among the open ones. The payout must fit within
+    /// `max_payout_bps` of the vault balance not already reserved for open
+    /// bets, and within the per-slot exposure cap.
+    pub fn reserve_payout(&mut self, bet: &Bet, vault_amount: u64, clock: &Clock) -> Result<()> {
+        let payout = bet.house_liability();
+        let free_liquidity = vault_amount.saturating_sub(self.pending_payouts);
+        let max_payout = (free_liquidity as u128 * self.max_payout_bps as u128 / 10_000) as u64;
+        require!(payout <= max_payout, CoinFlipError::InsufficientHouseLiquidity);
+
+        if self.exposure_slot != clock.slot {
+            self.exposure_slot = clock.slot;
+            self.slot_exposure = 0;
+        }
+        let slot_exposure = self
+            .slot_exposure
+            .checked_add(payout)
+            .ok_or(CoinFlipError::MathOverflow)?;
+        require!(
+            slot_exposure <= self.max_slot_exposure,
+            CoinFlipError::SlotExposureExceeded
+        );
+
+        let pending_payouts = self
+            .pending_payouts
+            .checked_add(payout)
+            .ok_or(CoinFlipError::MathOverflow)?;
+        let open_stakes = self
+            .open_stakes
+            .checked_add(bet.bet_amount)
+            .ok_or(CoinFlipError::MathOverflow)?;
+
+        self.slot_exposure = slot_exposure;
+        self.pending_payouts = pending_payouts;
+        self.open_stakes = open_stakes;
+        Ok(())
+    }
+
+    /// Drops a settled bet's reservation and stake, whatever the outcome.
+    pub fn release_payout(&mut self, bet: &Bet) {
+        self.pending_payouts = self.pending_payouts.saturating_sub(bet.house_liability());
+        self.open_stakes = self.open_stakes.saturating_sub(bet.bet_amount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::tests::game_state;
+    use crate::GameKind;
+
+    fn bet(bet_amount: u64, payout: u64) -> Bet {
+        Bet {
+            player: Pubkey::new_unique(),
+            game_state: Pubkey::new_unique(),
+            bet_id: 0,
+            bet_amount,
+            payout,
+            kind: GameKind::CoinFlip,
+            choice: true,
+            roll_under: 0,
+            commit_slot: 0,
+            house_seed: None,
+            client_seed: [0; 32],
+            bump: 255,
+            escrow_bump: 255,
+        }
+    }
+
+    fn clock_at_slot(slot: u64) -> Clock {
+        Clock { slot, ..Clock::default() }
+    }
+
+    #[test]
+    fn payouts_fit_within_the_unreserved_vault_share() {
+        let mut state = game_state();
+        // 2% of 10_000 free liquidity allows a 200 liability
+        state.reserve_payout(&bet(1_000, 1_200), 10_000, &clock_at_slot(1)).unwrap();
+        assert_eq!((state.pending_payouts, state.open_stakes), (200, 1_000));
+        assert_eq!(state.withdrawable(10_000), 9_800);
+        assert_eq!(state.deposit_assets(10_000), 11_000);
+
+        // The reservation shrinks what the next bet may take
+        assert!(state.reserve_payout(&bet(1_000, 1_200), 10_000, &clock_at_slot(1)).is_err());
+        state.reserve_payout(&bet(1_000, 1_196), 10_000, &clock_at_slot(1)).unwrap();
+
+        state.release_payout(&bet(1_000, 1_200));
+        state.release_payout(&bet(1_000, 1_196));
+        assert_eq!((state.pending_payouts, state.open_stakes), (0, 0));
+    }
+
+    #[test]
+    fn slot_exposure_resets_each_slot() {
+        let mut state = game_state();
+        state.max_slot_exposure = 300;
+        state.reserve_payout(&bet(1_000, 1_200), 1_000_000, &clock_at_slot(1)).unwrap();
+        assert!(state.reserve_payout(&bet(1_000, 1_200), 1_000_000, &clock_at_slot(1)).is_err());
+        state.reserve_payout(&bet(1_000, 1_200), 1_000_000, &clock_at_slot(2)).unwrap();
+        assert_eq!(state.slot_exposure, 200);
+        assert_eq!(state.pending_payouts, 400);
+    }
+}
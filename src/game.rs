@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{coin_flip, dice_roll, CoinFlipError};
+
+pub const ROLL_RANGE: u16 = 10_000; // Dice rolls land in 0..ROLL_RANGE, in bps
+pub const COIN_FLIP_WIN_CHANCE_BPS: u16 = 5_000;
+pub const MIN_ROLL_UNDER: u16 = 100; // 1% win chance
+pub const MAX_ROLL_UNDER: u16 = 9_500; // 95% win chance
+pub const DEFAULT_MAX_WIN: u64 = 10_000_000_000; // Net winnings on a single bet
+pub const GAME_KIND_COUNT: usize = 2;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum GameKind {
+    CoinFlip,
+    Dice, // Roll under N out of ROLL_RANGE
+}
+
+impl GameKind {
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// What a bet's entropy decided. Coin flips read `flip_result`, dice read `roll`.
+#[derive(Clone, Copy, Debug)]
+pub struct Draw {
+    pub flip_result: bool,
+    pub roll: u16,
+}
+
+impl Draw {
+    pub fn from_entropy(entropy: &[u8; 32]) -> Self {
+        Self {
+            flip_result: coin_flip(entropy),
+            roll: dice_roll(entropy),
+        }
+    }
+}
+
+/// Rejects dice targets outside the offered range.
+pub fn validate_roll_under(roll_under: u16) -> Result<()> {
+    require!(
+        (MIN_ROLL_UNDER..=MAX_ROLL_UNDER).contains(&roll_under),
+        CoinFlipError::InvalidRollTarget
+    );
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{seed_commitment, CoinFlipError};
+
+pub const REVEAL_TIMEOUT_SLOTS: u64 = 1500; // ~10 minutes for the house to reveal
+
+/// A hashed server seed posted by the house ahead of time. Each seed backs at
+/// most one bet, and both accounts close once the seed is revealed.
+#[account]
+#[derive(InitSpace)]
+pub struct HouseSeed {
+    pub game_state: Pubkey,
+    pub seed_index: u64,
+    pub commitment: [u8; 32], // hash(server_seed)
+    pub bet: Option<Pubkey>, // Bet that consumed this seed
+    pub bump: u8,
+}
+
+impl HouseSeed {
+    pub fn verify_reveal(&self, server_seed: &[u8; 32]) -> Result<()> {
+        require!(
+            seed_commitment(server_seed) == self.commitment,
+            CoinFlipError::InvalidServerSeed
+        );
+        Ok(())
+    }
+}
@@ -1,8 +1,35 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_spl::token::{
+    self, spl_token::native_mint, Burn, CloseAccount, Mint, MintTo, SyncNative, Token, TokenAccount, Transfer,
+};
+
+pub mod admin;
+pub mod bet;
+pub mod exposure;
+pub mod game;
+pub mod house_seed;
+pub mod liquidity;
+pub mod player_profile;
+pub mod randomness;
+pub mod settlement;
+
+pub use admin::*;
+pub use bet::*;
+pub use exposure::*;
+pub use game::*;
+pub use house_seed::*;
+pub use liquidity::*;
+pub use player_profile::*;
+pub use randomness::*;
+pub use settlement::*;
 
 declare_id!("CoinF1ipGameProgram11111111111111111111111");
 
+pub const HOUSE_SEED: &[u8] = b"house"; // Authority PDA over the house vault
+pub const HOUSE_VAULT_SEED: &[u8] = b"house_vault";
+
 #[program]
 pub mod coin_flip_game {
     use super::*;
@@ -14,87 +41,478 @@ pub mod coin_flip_game {
         game_state.max_bet = 1_000_000_000; // 1 SOL in lamports
         game_state.total_games = 0;
         game_state.house_balance = 0;
+        game_state.authority = ctx.accounts.authority.key();
+        game_state.paused = false;
+        game_state.house_signer = ctx.accounts.authority.key();
+        game_state.mint = ctx.accounts.mint.key();
+        game_state.native_sol = ctx.accounts.mint.key() == native_mint::ID;
+        game_state.house_bump = ctx.bumps.house_authority;
+        game_state.vault_bump = ctx.bumps.house_vault;
+        game_state.max_payout_bps = DEFAULT_MAX_PAYOUT_BPS;
+        game_state.max_slot_exposure = DEFAULT_MAX_SLOT_EXPOSURE;
+        game_state.pending_payouts = 0;
+        game_state.open_stakes = 0;
+        game_state.max_win = [DEFAULT_MAX_WIN; GAME_KIND_COUNT];
+        game_state.lp_mint = None;
+        game_state.pending_config = None;
+        Ok(())
+    }
+
+    // The house posts seed commitments ahead of demand; each one can back a single bet
+    pub fn commit_house_seed(
+        ctx: Context<CommitHouseSeed>,
+        seed_index: u64,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        let house_seed = &mut ctx.accounts.house_seed;
+        house_seed.set_inner(HouseSeed {
+            game_state: ctx.accounts.game_state.key(),
+            seed_index,
+            commitment,
+            bet: None,
+            bump: ctx.bumps.house_seed,
+        });
+
+        emit!(HouseSeedCommitted {
+            house_seed: house_seed.key(),
+            seed_index,
+            commitment,
+        });
+
         Ok(())
     }
 
+    // Coin flip at even odds; see `open_bet` for the two-phase flow
     pub fn place_bet(
         ctx: Context<PlaceBet>,
+        bet_id: u64,
         bet_amount: u64,
         choice: bool, // true = heads, false = tails
+        client_seed: [u8; 32],
+    ) -> Result<()> {
+        open_bet(ctx, bet_id, bet_amount, GameKind::CoinFlip, choice, 0, client_seed)
+    }
+
+    // Same two-phase flow as `place_bet`; the bet wins when the roll lands
+    // below `roll_under` out of ROLL_RANGE and pays in proportion to the risk
+    pub fn place_dice_bet(
+        ctx: Context<PlaceBet>,
+        bet_id: u64,
+        bet_amount: u64,
+        roll_under: u16,
+        client_seed: [u8; 32],
+    ) -> Result<()> {
+        validate_roll_under(roll_under)?;
+        open_bet(ctx, bet_id, bet_amount, GameKind::Dice, false, roll_under, client_seed)
+    }
+
+    // Phase two, permissionless: the house cranks every bet, and a player can
+    // settle their own whenever the house does not. A bet is forfeited to the
+    // house only once its deciding hash has left SlotHashes, about 200 seconds
+    // after the target slot; refunding it would let a player who saw a losing
+    // hash wait it out and take their stake back.
+    pub fn settle_bet(ctx: Context<SettleBet>) -> Result<()> {
+        let clock = Clock::get()?;
+        let bet = &ctx.accounts.bet;
+        require!(bet.house_seed.is_none(), CoinFlipError::WrongSettlementPath);
+        require!(clock.slot > bet.target_slot(), CoinFlipError::RandomnessNotReady);
+
+        let outcome = match slot_hash_at_or_after(&ctx.accounts.slot_hashes, bet.target_slot())? {
+            SlotHashLookup::Pending => return err!(CoinFlipError::RandomnessNotReady),
+            SlotHashLookup::Found(slot_hash) => {
+                record_result(&mut ctx.accounts.game_state, bet, &slot_entropy(&slot_hash, &bet.key()))
+            }
+            SlotHashLookup::Expired => {
+                emit!(BetForfeited {
+                    player: bet.player,
+                    bet: bet.key(),
+                    bet_amount: bet.bet_amount,
+                });
+                BetOutcome::Lost
+            }
+        };
+
+        ctx.accounts.settlement().settle(outcome)?;
+        ctx.accounts.player_profile.record_settlement(&ctx.accounts.bet, outcome, clock.unix_timestamp);
+        ctx.accounts.game_state.release_payout(&ctx.accounts.bet);
+        sync_house_balance(&mut ctx.accounts.game_state, &mut ctx.accounts.house_vault)
+    }
+
+    pub fn reveal_house_seed(ctx: Context<RevealHouseSeed>, server_seed: [u8; 32]) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.house_seed.verify_reveal(&server_seed)?;
+
+        let bet = &ctx.accounts.bet;
+        let entropy = commit_reveal_entropy(&server_seed, &bet.client_seed, bet.bet_id);
+
+        emit!(HouseSeedRevealed {
+            house_seed: ctx.accounts.house_seed.key(),
+            bet: bet.key(),
+            server_seed,
+        });
+
+        let outcome = record_result(&mut ctx.accounts.game_state, bet, &entropy);
+        ctx.accounts.settlement().settle(outcome)?;
+        ctx.accounts.player_profile.record_settlement(&ctx.accounts.bet, outcome, clock.unix_timestamp);
+        ctx.accounts.game_state.release_payout(&ctx.accounts.bet);
+        sync_house_balance(&mut ctx.accounts.game_state, &mut ctx.accounts.house_vault)
+    }
+
+    // A house that sees a losing reveal coming could simply stay silent, so
+    // missing the reveal window pays the player as if they had won
+    pub fn claim_unrevealed_bet(ctx: Context<ClaimUnrevealedBet>) -> Result<()> {
+        let clock = Clock::get()?;
+        let bet = &ctx.accounts.bet;
+        require!(bet.reveal_timed_out(&clock), CoinFlipError::RevealWindowOpen);
+
+        emit!(UnrevealedBetClaimed {
+            player: bet.player,
+            bet: bet.key(),
+            house_seed: ctx.accounts.house_seed.key(),
+            payout: bet.payout,
+        });
+
+        ctx.accounts.settlement().settle(BetOutcome::Won)?;
+        ctx.accounts.player_profile.record_settlement(&ctx.accounts.bet, BetOutcome::Won, clock.unix_timestamp);
+        ctx.accounts.game_state.release_payout(&ctx.accounts.bet);
+        sync_house_balance(&mut ctx.accounts.game_state, &mut ctx.accounts.house_vault)
+    }
+
+    pub fn fund_house(ctx: Context<FundHouse>, amount: u64) -> Result<()> {
+        deposit(
+            &ctx.accounts.game_state,
+            &ctx.accounts.funder,
+            ctx.accounts.funder_token_account.as_ref(),
+            &ctx.accounts.house_vault,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+        sync_house_balance(&mut ctx.accounts.game_state, &mut ctx.accounts.house_vault)?;
+
+        Ok(())
+    }
+
+    // Every player opens a profile before their first bet so limits always apply
+    pub fn open_player_profile(ctx: Context<OpenPlayerProfile>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.player_profile.set_inner(PlayerProfile {
+            player: ctx.accounts.player.key(),
+            game_state: ctx.accounts.game_state.key(),
+            total_wagered: 0,
+            total_bets: 0,
+            net_pnl: 0,
+            open_stake: 0,
+            day: clock.unix_timestamp.div_euclid(SECONDS_PER_DAY),
+            day_pnl: 0,
+            day_bets: 0,
+            daily_loss_limit: 0,
+            daily_bet_limit: 0,
+            pending_daily_loss_limit: 0,
+            pending_daily_bet_limit: 0,
+            pending_limits_at: 0,
+            excluded_until: 0,
+            bump: ctx.bumps.player_profile,
+        });
+        Ok(())
+    }
+
+    pub fn set_player_limits(
+        ctx: Context<UpdatePlayerProfile>,
+        daily_loss_limit: u64, // 0 = no limit
+        daily_bet_limit: u32,  // 0 = no limit
     ) -> Result<()> {
+        let clock = Clock::get()?;
+        let profile = &mut ctx.accounts.player_profile;
+        let effective_at = profile.set_limits(daily_loss_limit, daily_bet_limit, clock.unix_timestamp);
+
+        emit!(PlayerLimitsUpdated {
+            player: profile.player,
+            daily_loss_limit,
+            daily_bet_limit,
+            effective_at,
+        });
+
+        Ok(())
+    }
+
+    // Covers both short cooldowns and long self-exclusions
+    pub fn self_exclude(ctx: Context<UpdatePlayerProfile>, duration_seconds: i64) -> Result<()> {
+        let clock = Clock::get()?;
+        let profile = &mut ctx.accounts.player_profile;
+        profile.self_exclude(duration_seconds, clock.unix_timestamp)?;
+
+        emit!(PlayerSelfExcluded {
+            player: profile.player,
+            excluded_until: profile.excluded_until,
+        });
+
+        Ok(())
+    }
+
+    pub fn update_config(ctx: Context<AdminConfig>, update: ConfigUpdate) -> Result<()> {
+        let clock = Clock::get()?;
+        let game_state = &mut ctx.accounts.game_state;
+        if let Some(pending) = game_state.update_config(&update, clock.unix_timestamp)? {
+            emit!(ConfigChangeQueued {
+                game_state: game_state.key(),
+                house_edge: pending.house_edge,
+                house_signer: pending.house_signer,
+                max_payout_bps: pending.max_payout_bps,
+                effective_at: pending.effective_at,
+            });
+        }
+
+        emit_config_updated(game_state);
+        Ok(())
+    }
+
+    // Applies risk terms queued while LP shares are live, once their timelock is over
+    pub fn apply_pending_config(ctx: Context<AdminConfig>) -> Result<()> {
+        let clock = Clock::get()?;
         let game_state = &mut ctx.accounts.game_state;
-        
+        game_state.apply_pending_config(clock.unix_timestamp)?;
+
+        emit_config_updated(game_state);
+        Ok(())
+    }
+
+    // Pausing only stops new bets; open bets can still settle, reveal or be claimed
+    pub fn pause(ctx: Context<AdminConfig>) -> Result<()> {
+        set_paused(&mut ctx.accounts.game_state, true)
+    }
+
+    pub fn unpause(ctx: Context<AdminConfig>) -> Result<()> {
+        set_paused(&mut ctx.accounts.game_state, false)
+    }
+
+    // Only liquidity not reserved for open bets can leave the vault. Once the
+    // bankroll is split into LP shares, the authority exits through its shares.
+    pub fn withdraw_house(ctx: Context<WithdrawHouse>, amount: u64) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        require!(game_state.lp_mint.is_none(), CoinFlipError::LiquiditySharesActive);
+        require!(amount > 0, CoinFlipError::InvalidWithdrawAmount);
         require!(
-            bet_amount >= game_state.min_bet && bet_amount <= game_state.max_bet,
-            CoinFlipError::InvalidBetAmount
+            amount <= game_state.withdrawable(ctx.accounts.house_vault.amount),
+            CoinFlipError::InsufficientHouseLiquidity
         );
 
-        // Generate pseudo-random number using recent slot hash
-        let clock = Clock::get()?;
-        let recent_slot_hash = clock.slot;
-        let random_seed = recent_slot_hash
-            .wrapping_mul(game_state.total_games + 1)
-            .wrapping_add(bet_amount);
-        
-        let flip_result = (random_seed % 2) == 0; // true = heads, false = tails
-        let player_won = choice == flip_result;
-
-        if player_won {
-            // Calculate payout (accounting for house edge)
-            let house_fee = (bet_amount * game_state.house_edge) / 10000;
-            let payout = bet_amount * 2 - house_fee;
-            
-            // Transfer winnings to player
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.house_vault.to_account_info(),
-                to: ctx.accounts.player_token_account.to_account_info(),
+        let game_state_key = game_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOUSE_SEED,
+            game_state_key.as_ref(),
+            &[game_state.house_bump],
+        ]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.house_vault.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.house_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+        sync_house_balance(&mut ctx.accounts.game_state, &mut ctx.accounts.house_vault)?;
+
+        emit!(HouseWithdrawn {
+            game_state: game_state_key,
+            destination: ctx.accounts.destination.key(),
+            amount,
+            house_balance: ctx.accounts.game_state.house_balance,
+        });
+
+        Ok(())
+    }
+
+    // Turns the bankroll into LP shares. The authority receives shares 1:1 for
+    // the current vault so later depositors buy in at the vault's value, less
+    // MINIMUM_LIQUIDITY shares locked in an account nothing can withdraw from.
+    pub fn enable_liquidity_shares(ctx: Context<EnableLiquidityShares>) -> Result<()> {
+        let vault_amount = ctx.accounts.house_vault.amount;
+        require!(vault_amount >= MINIMUM_LIQUIDITY, CoinFlipError::InvalidLiquidityAmount);
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.lp_mint = Some(ctx.accounts.lp_mint.key());
+        game_state.lp_mint_bump = ctx.bumps.lp_mint;
+
+        let game_state_key = game_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOUSE_SEED,
+            game_state_key.as_ref(),
+            &[game_state.house_bump],
+        ]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            to: ctx.accounts.dead_shares.to_account_info(),
+            authority: ctx.accounts.house_authority.to_account_info(),
+        };
+        token::mint_to(
+            CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds),
+            MINIMUM_LIQUIDITY,
+        )?;
+
+        let shares = vault_amount - MINIMUM_LIQUIDITY;
+        if shares > 0 {
+            let cpi_accounts = MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.authority_shares.to_account_info(),
                 authority: ctx.accounts.house_authority.to_account_info(),
             };
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            token::transfer(cpi_ctx, payout)?;
-            
-            game_state.house_balance = game_state.house_balance.saturating_sub(payout - bet_amount);
-        } else {
-            // Transfer bet to house
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.player_token_account.to_account_info(),
-                to: ctx.accounts.house_vault.to_account_info(),
-                authority: ctx.accounts.player.to_account_info(),
-            };
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            token::transfer(cpi_ctx, bet_amount)?;
-            
-            game_state.house_balance = game_state.house_balance.saturating_add(bet_amount);
+            token::mint_to(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds), shares)?;
         }
 
-        game_state.total_games += 1;
+        emit!(LiquidityDeposited {
+            game_state: game_state_key,
+            provider: ctx.accounts.authority.key(),
+            amount: vault_amount,
+            shares,
+        });
+
+        Ok(())
+    }
 
-        emit!(GameResult {
-            player: ctx.accounts.player.key(),
-            bet_amount,
-            choice,
-            flip_result,
-            player_won,
-            game_number: game_state.total_games,
+    // Lost stakes grow the vault and winnings shrink it, so house results
+    // accrue to every share pro rata. Deposits buy in as if every open bet
+    // lost, so outcomes already decided but not yet settled never favour
+    // the depositor.
+    pub fn deposit_liquidity(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+        let deposit_assets = ctx.accounts.game_state.deposit_assets(ctx.accounts.house_vault.amount);
+        let shares = shares_for_deposit(amount, deposit_assets, ctx.accounts.lp_mint.supply)?;
+        require!(shares > 0, CoinFlipError::InvalidLiquidityAmount);
+
+        deposit(
+            &ctx.accounts.game_state,
+            &ctx.accounts.provider,
+            ctx.accounts.provider_token_account.as_ref(),
+            &ctx.accounts.house_vault,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+            amount,
+        )?;
+
+        let game_state_key = ctx.accounts.game_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOUSE_SEED,
+            game_state_key.as_ref(),
+            &[ctx.accounts.game_state.house_bump],
+        ]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            to: ctx.accounts.provider_shares.to_account_info(),
+            authority: ctx.accounts.house_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::mint_to(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds), shares)?;
+        sync_house_balance(&mut ctx.accounts.game_state, &mut ctx.accounts.house_vault)?;
+
+        emit!(LiquidityDeposited {
+            game_state: game_state_key,
+            provider: ctx.accounts.provider.key(),
+            amount,
+            shares,
         });
 
         Ok(())
     }
 
-    pub fn fund_house(ctx: Context<FundHouse>, amount: u64) -> Result<()> {
+    // Locks shares for LP_WITHDRAWAL_DELAY so LPs cannot exit just ahead of
+    // a large payout they see coming
+    pub fn request_lp_withdrawal(ctx: Context<RequestLpWithdrawal>, shares: u64) -> Result<()> {
+        require!(shares > 0, CoinFlipError::InvalidLiquidityAmount);
+        let clock = Clock::get()?;
+
         let cpi_accounts = Transfer {
-            from: ctx.accounts.funder_token_account.to_account_info(),
-            to: ctx.accounts.house_vault.to_account_info(),
-            authority: ctx.accounts.funder.to_account_info(),
+            from: ctx.accounts.provider_shares.to_account_info(),
+            to: ctx.accounts.lp_escrow.to_account_info(),
+            authority: ctx.accounts.provider.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), shares)?;
 
-        let game_state = &mut ctx.accounts.game_state;
-        game_state.house_balance = game_state.house_balance.saturating_add(amount);
+        let withdrawal = &mut ctx.accounts.withdrawal;
+        withdrawal.set_inner(LpWithdrawal {
+            provider: ctx.accounts.provider.key(),
+            game_state: ctx.accounts.game_state.key(),
+            shares,
+            requested_at: clock.unix_timestamp,
+            bump: ctx.bumps.withdrawal,
+            escrow_bump: ctx.bumps.lp_escrow,
+        });
+
+        emit!(LpWithdrawalRequested {
+            game_state: withdrawal.game_state,
+            provider: withdrawal.provider,
+            shares,
+            available_at: withdrawal.available_at(),
+        });
+
+        Ok(())
+    }
+
+    // SOL games pay lamports the way bet settlement does: the amount passes
+    // through a wrapped SOL account that is closed into the provider's wallet
+    pub fn complete_lp_withdrawal(ctx: Context<CompleteLpWithdrawal>) -> Result<()> {
+        let clock = Clock::get()?;
+        let withdrawal = &ctx.accounts.withdrawal;
+        require!(
+            clock.unix_timestamp >= withdrawal.available_at(),
+            CoinFlipError::LpWithdrawalLocked
+        );
+
+        let game_state = &ctx.accounts.game_state;
+        let net_assets = game_state.withdrawable(ctx.accounts.house_vault.amount);
+        let amount = amount_for_shares(withdrawal.shares, net_assets, ctx.accounts.lp_mint.supply)?;
+        require!(amount > 0, CoinFlipError::InsufficientHouseLiquidity);
+        let destination = match (game_state.native_sol, &ctx.accounts.destination, &ctx.accounts.sol_payout) {
+            (false, Some(destination), None) => destination.to_account_info(),
+            (true, None, Some(sol_payout)) => sol_payout.to_account_info(),
+            _ => return err!(CoinFlipError::InvalidPayoutAccount),
+        };
+
+        let game_state_key = game_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOUSE_SEED,
+            game_state_key.as_ref(),
+            &[game_state.house_bump],
+        ]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            from: ctx.accounts.lp_escrow.to_account_info(),
+            authority: ctx.accounts.house_authority.to_account_info(),
+        };
+        token::burn(
+            CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds),
+            withdrawal.shares,
+        )?;
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.lp_escrow.to_account_info(),
+            destination: ctx.accounts.provider.to_account_info(),
+            authority: ctx.accounts.house_authority.to_account_info(),
+        };
+        token::close_account(CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds))?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.house_vault.to_account_info(),
+            to: destination.clone(),
+            authority: ctx.accounts.house_authority.to_account_info(),
+        };
+        token::transfer(CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer_seeds), amount)?;
+        if ctx.accounts.sol_payout.is_some() {
+            let cpi_accounts = CloseAccount {
+                account: destination,
+                destination: ctx.accounts.provider.to_account_info(),
+                authority: ctx.accounts.house_authority.to_account_info(),
+            };
+            token::close_account(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds))?;
+        }
+        sync_house_balance(&mut ctx.accounts.game_state, &mut ctx.accounts.house_vault)?;
+
+        emit!(LpWithdrawalCompleted {
+            game_state: game_state_key,
+            provider: ctx.accounts.provider.key(),
+            shares: ctx.accounts.withdrawal.shares,
+            amount,
+        });
 
         Ok(())
     }
@@ -108,61 +526,985 @@ pub struct InitializeGame<'info> {
         space = 8 + GameState::INIT_SPACE
     )]
     pub game_state: Account<'info, GameState>,
+    pub mint: Account<'info, Mint>,
+    /// CHECK: House authority PDA; owns the vault and only signs
+    #[account(
+        seeds = [HOUSE_SEED, game_state.key().as_ref()],
+        bump
+    )]
+    pub house_authority: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = house_authority,
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump
+    )]
+    pub house_vault: Account<'info, TokenAccount>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(seed_index: u64)]
+pub struct CommitHouseSeed<'info> {
+    #[account(has_one = house_signer)]
+    pub game_state: Account<'info, GameState>,
+    #[account(
+        init,
+        payer = house_signer,
+        space = 8 + HouseSeed::INIT_SPACE,
+        seeds = [b"house_seed", game_state.key().as_ref(), &seed_index.to_le_bytes()],
+        bump
+    )]
+    pub house_seed: Account<'info, HouseSeed>,
+    #[account(mut)]
+    pub house_signer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+#[instruction(bet_id: u64)]
 pub struct PlaceBet<'info> {
     #[account(mut)]
     pub game_state: Account<'info, GameState>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + Bet::INIT_SPACE,
+        seeds = [b"bet", game_state.key().as_ref(), player.key().as_ref(), &bet_id.to_le_bytes()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+    // Present only for commit-reveal bets
+    #[account(
+        mut,
+        has_one = game_state,
+        seeds = [b"house_seed", game_state.key().as_ref(), &house_seed.seed_index.to_le_bytes()],
+        bump = house_seed.bump
+    )]
+    pub house_seed: Option<Account<'info, HouseSeed>>,
+    #[account(
+        init,
+        payer = player,
+        token::mint = mint,
+        token::authority = bet,
+        seeds = [BET_ESCROW_SEED, bet.key().as_ref()],
+        bump
+    )]
+    pub bet_escrow: Account<'info, TokenAccount>,
+    #[account(address = game_state.mint @ CoinFlipError::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [PLAYER_PROFILE_SEED, game_state.key().as_ref(), player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
     #[account(mut)]
     pub player: Signer<'info>,
+    #[account(
+        mut,
+        constraint = player_token_account.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>, // Token games only
+    #[account(
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump = game_state.vault_bump,
+        constraint = house_vault.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub house_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SettleBet<'info> {
     #[account(mut)]
-    pub player_token_account: Account<'info, TokenAccount>,
+    pub game_state: Account<'info, GameState>,
+    #[account(
+        mut,
+        has_one = player,
+        has_one = game_state,
+        close = player,
+        seeds = [b"bet", game_state.key().as_ref(), player.key().as_ref(), &bet.bet_id.to_le_bytes()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+    #[account(
+        mut,
+        seeds = [BET_ESCROW_SEED, bet.key().as_ref()],
+        bump = bet.escrow_bump
+    )]
+    pub bet_escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [PLAYER_PROFILE_SEED, game_state.key().as_ref(), bet.player.as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    /// CHECK: Receives the bet account rent; must be the bet's player
     #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+    // Settlement is permissionless, so winnings may only go to the player
+    #[account(
+        mut,
+        token::authority = player,
+        constraint = player_token_account.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>, // Token games only
+    #[account(
+        mut,
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump = game_state.vault_bump,
+        constraint = house_vault.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
     pub house_vault: Account<'info, TokenAccount>,
-    /// CHECK: House authority PDA
-    pub house_authority: AccountInfo<'info>,
+    /// CHECK: House authority PDA; only signs
+    #[account(
+        seeds = [HOUSE_SEED, game_state.key().as_ref()],
+        bump = game_state.house_bump
+    )]
+    pub house_authority: UncheckedAccount<'info>,
+    /// CHECK: SlotHashes sysvar, parsed manually
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct RevealHouseSeed<'info> {
+    #[account(
+        mut,
+        has_one = house_signer
+    )]
+    pub game_state: Account<'info, GameState>,
+    #[account(
+        mut,
+        has_one = game_state,
+        close = house_signer,
+        seeds = [b"house_seed", game_state.key().as_ref(), &house_seed.seed_index.to_le_bytes()],
+        bump = house_seed.bump
+    )]
+    pub house_seed: Account<'info, HouseSeed>,
+    #[account(
+        mut,
+        has_one = player,
+        has_one = game_state,
+        close = player,
+        constraint = bet.house_seed == Some(house_seed.key()) @ CoinFlipError::WrongSettlementPath,
+        seeds = [b"bet", game_state.key().as_ref(), player.key().as_ref(), &bet.bet_id.to_le_bytes()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+    #[account(
+        mut,
+        seeds = [BET_ESCROW_SEED, bet.key().as_ref()],
+        bump = bet.escrow_bump
+    )]
+    pub bet_escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [PLAYER_PROFILE_SEED, game_state.key().as_ref(), bet.player.as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    /// CHECK: Receives the bet account rent; must be the bet's player
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::authority = player,
+        constraint = player_token_account.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>, // Token games only
+    #[account(
+        mut,
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump = game_state.vault_bump,
+        constraint = house_vault.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub house_vault: Account<'info, TokenAccount>,
+    /// CHECK: House authority PDA; only signs
+    #[account(
+        seeds = [HOUSE_SEED, game_state.key().as_ref()],
+        bump = game_state.house_bump
+    )]
+    pub house_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub house_signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimUnrevealedBet<'info> {
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+    // The forfeited seed's rent goes to the player as well
+    #[account(
+        mut,
+        has_one = game_state,
+        close = player,
+        seeds = [b"house_seed", game_state.key().as_ref(), &house_seed.seed_index.to_le_bytes()],
+        bump = house_seed.bump
+    )]
+    pub house_seed: Account<'info, HouseSeed>,
+    #[account(
+        mut,
+        has_one = player,
+        has_one = game_state,
+        close = player,
+        constraint = bet.house_seed == Some(house_seed.key()) @ CoinFlipError::WrongSettlementPath,
+        seeds = [b"bet", game_state.key().as_ref(), player.key().as_ref(), &bet.bet_id.to_le_bytes()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
+    #[account(
+        mut,
+        seeds = [BET_ESCROW_SEED, bet.key().as_ref()],
+        bump = bet.escrow_bump
+    )]
+    pub bet_escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [PLAYER_PROFILE_SEED, game_state.key().as_ref(), bet.player.as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        token::authority = player,
+        constraint = player_token_account.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub player_token_account: Option<Account<'info, TokenAccount>>, // Token games only
+    #[account(
+        mut,
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump = game_state.vault_bump,
+        constraint = house_vault.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub house_vault: Account<'info, TokenAccount>,
+    /// CHECK: House authority PDA; only signs
+    #[account(
+        seeds = [HOUSE_SEED, game_state.key().as_ref()],
+        bump = game_state.house_bump
+    )]
+    pub house_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SettleBet<'info> {
+    fn settlement(&self) -> EscrowSettlement<'_, 'info> {
+        EscrowSettlement {
+            game_state: &self.game_state,
+            bet: &self.bet,
+            bet_escrow: &self.bet_escrow,
+            house_vault: &self.house_vault,
+            house_authority: &self.house_authority,
+            player: self.player.to_account_info(),
+            player_token_account: self.player_token_account.as_ref(),
+            token_program: &self.token_program,
+        }
+    }
+}
+
+impl<'info> RevealHouseSeed<'info> {
+    fn settlement(&self) -> EscrowSettlement<'_, 'info> {
+        EscrowSettlement {
+            game_state: &self.game_state,
+            bet: &self.bet,
+            bet_escrow: &self.bet_escrow,
+            house_vault: &self.house_vault,
+            house_authority: &self.house_authority,
+            player: self.player.to_account_info(),
+            player_token_account: self.player_token_account.as_ref(),
+            token_program: &self.token_program,
+        }
+    }
+}
+
+impl<'info> ClaimUnrevealedBet<'info> {
+    fn settlement(&self) -> EscrowSettlement<'_, 'info> {
+        EscrowSettlement {
+            game_state: &self.game_state,
+            bet: &self.bet,
+            bet_escrow: &self.bet_escrow,
+            house_vault: &self.house_vault,
+            house_authority: &self.house_authority,
+            player: self.player.to_account_info(),
+            player_token_account: self.player_token_account.as_ref(),
+            token_program: &self.token_program,
+        }
+    }
+}
+
 #[derive(Accounts)]
 pub struct FundHouse<'info> {
     #[account(mut)]
     pub game_state: Account<'info, GameState>,
     #[account(mut)]
     pub funder: Signer<'info>,
+    // Token games only; SOL games fund from the funder's wallet
+    #[account(mut)]
+    pub funder_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump = game_state.vault_bump,
+        constraint = house_vault.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub house_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPlayerProfile<'info> {
+    pub game_state: Account<'info, GameState>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [PLAYER_PROFILE_SEED, game_state.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Limits are self-imposed, so only the player can change them
+#[derive(Accounts)]
+pub struct UpdatePlayerProfile<'info> {
+    #[account(
+        mut,
+        has_one = player,
+        seeds = [PLAYER_PROFILE_SEED, player_profile.game_state.as_ref(), player.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminConfig<'info> {
+    #[account(mut, has_one = authority)]
+    pub game_state: Account<'info, GameState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawHouse<'info> {
+    #[account(mut, has_one = authority)]
+    pub game_state: Account<'info, GameState>,
+    pub authority: Signer<'info>,
+    /// CHECK: House authority PDA; only signs
+    #[account(
+        seeds = [HOUSE_SEED, game_state.key().as_ref()],
+        bump = game_state.house_bump
+    )]
+    pub house_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump = game_state.vault_bump,
+        constraint = house_vault.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub house_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct EnableLiquidityShares<'info> {
+    #[account(mut, has_one = authority)]
+    pub game_state: Account<'info, GameState>,
+    #[account(address = game_state.mint @ CoinFlipError::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = mint.decimals,
+        mint::authority = house_authority,
+        seeds = [LP_MINT_SEED, game_state.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        token::mint = lp_mint,
+        token::authority = authority
+    )]
+    pub authority_shares: Account<'info, TokenAccount>,
+    // Owned by the house PDA, which never signs a transfer out of it
+    #[account(
+        init,
+        payer = authority,
+        token::mint = lp_mint,
+        token::authority = house_authority,
+        seeds = [LP_DEAD_SHARES_SEED, game_state.key().as_ref()],
+        bump
+    )]
+    pub dead_shares: Account<'info, TokenAccount>,
+    /// CHECK: House authority PDA; mints shares and only signs
+    #[account(
+        seeds = [HOUSE_SEED, game_state.key().as_ref()],
+        bump = game_state.house_bump
+    )]
+    pub house_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump = game_state.vault_bump
+    )]
+    pub house_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct DepositLiquidity<'info> {
+    #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [LP_MINT_SEED, game_state.key().as_ref()],
+        bump = game_state.lp_mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = provider
+    )]
+    pub provider_shares: Account<'info, TokenAccount>,
+    // Token games only; SOL games deposit from the provider's wallet
+    #[account(
+        mut,
+        constraint = provider_token_account.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub provider_token_account: Option<Account<'info, TokenAccount>>,
+    /// CHECK: House authority PDA; mints shares and only signs
+    #[account(
+        seeds = [HOUSE_SEED, game_state.key().as_ref()],
+        bump = game_state.house_bump
+    )]
+    pub house_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump = game_state.vault_bump
+    )]
+    pub house_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestLpWithdrawal<'info> {
+    pub game_state: Account<'info, GameState>,
+    #[account(
+        seeds = [LP_MINT_SEED, game_state.key().as_ref()],
+        bump = game_state.lp_mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = provider,
+        space = 8 + LpWithdrawal::INIT_SPACE,
+        seeds = [LP_WITHDRAWAL_SEED, game_state.key().as_ref(), provider.key().as_ref()],
+        bump
+    )]
+    pub withdrawal: Account<'info, LpWithdrawal>,
+    #[account(
+        init,
+        payer = provider,
+        token::mint = lp_mint,
+        token::authority = house_authority,
+        seeds = [LP_ESCROW_SEED, withdrawal.key().as_ref()],
+        bump
+    )]
+    pub lp_escrow: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = provider
+    )]
+    pub provider_shares: Account<'info, TokenAccount>,
+    /// CHECK: House authority PDA; owns the locked shares and only signs
+    #[account(
+        seeds = [HOUSE_SEED, game_state.key().as_ref()],
+        bump = game_state.house_bump
+    )]
+    pub house_authority: UncheckedAccount<'info>,
     #[account(mut)]
-    pub funder_token_account: Account<'info, TokenAccount>,
+    pub provider: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteLpWithdrawal<'info> {
     #[account(mut)]
+    pub game_state: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [LP_MINT_SEED, game_state.key().as_ref()],
+        bump = game_state.lp_mint_bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        has_one = provider,
+        has_one = game_state,
+        close = provider,
+        seeds = [LP_WITHDRAWAL_SEED, game_state.key().as_ref(), provider.key().as_ref()],
+        bump = withdrawal.bump
+    )]
+    pub withdrawal: Account<'info, LpWithdrawal>,
+    #[account(
+        mut,
+        seeds = [LP_ESCROW_SEED, withdrawal.key().as_ref()],
+        bump = withdrawal.escrow_bump
+    )]
+    pub lp_escrow: Account<'info, TokenAccount>,
+    /// CHECK: House authority PDA; only signs
+    #[account(
+        seeds = [HOUSE_SEED, game_state.key().as_ref()],
+        bump = game_state.house_bump
+    )]
+    pub house_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [HOUSE_VAULT_SEED, game_state.key().as_ref()],
+        bump = game_state.vault_bump
+    )]
     pub house_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination.mint == game_state.mint @ CoinFlipError::InvalidMint
+    )]
+    pub destination: Option<Account<'info, TokenAccount>>, // Token games only
+    // SOL games only: unwrapped into the provider's wallet within the instruction
+    #[account(
+        init,
+        payer = provider,
+        token::mint = mint,
+        token::authority = house_authority,
+        seeds = [LP_SOL_PAYOUT_SEED, withdrawal.key().as_ref()],
+        bump
+    )]
+    pub sol_payout: Option<Account<'info, TokenAccount>>,
+    #[account(address = game_state.mint @ CoinFlipError::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub provider: Signer<'info>,
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct GameState {
     pub house_edge: u16,        // Basis points (e.g., 200 = 2%)
-    pub min_bet: u64,           // Minimum bet in lamports
-    pub max_bet: u64,           // Maximum bet in lamports
+    pub min_bet: u64,           // Minimum bet in base units of the mint
+    pub max_bet: u64,           // Maximum bet in base units of the mint
     pub total_games: u64,       // Total number of games played
-    pub house_balance: u64,     // Current house balance
+    pub house_balance: u64,     // Vault balance, re-read after every transfer
+    pub house_signer: Pubkey,   // Commits and reveals server seeds
+    pub mint: Pubkey,           // Token the game is played in
+    pub native_sol: bool,       // Wrapped SOL mint: players bet and are paid in lamports
+    pub house_bump: u8,
+    pub vault_bump: u8,
+    pub max_payout_bps: u16,    // Largest payout as a share of unreserved vault liquidity
+    pub max_slot_exposure: u64, // Potential payouts accepted within a single slot
+    pub exposure_slot: u64,
+    pub slot_exposure: u64,
+    pub pending_payouts: u64,   // Reserved for bets that have not settled
+    pub open_stakes: u64,       // Escrowed stakes of bets that have not settled
+    pub authority: Pubkey,      // Updates config, pauses and withdraws
+    pub paused: bool,           // Blocks new bets only
+    pub max_win: [u64; GAME_KIND_COUNT], // Net winnings allowed on one bet, per GameKind
+    pub lp_mint: Option<Pubkey>, // Set once the bankroll is split into LP shares
+    pub lp_mint_bump: u8,
+    pub pending_config: Option<PendingConfig>, // Risk terms waiting out LP_CONFIG_TIMELOCK
+}
+
+#[event]
+pub struct BetPlaced {
+    pub player: Pubkey,
+    pub bet: Pubkey,
+    pub bet_amount: u64,
+    pub kind: GameKind,
+    pub choice: bool,
+    pub roll_under: u16,
+    pub commit_slot: u64,
+    pub house_seed: Option<Pubkey>,
+}
+
+#[event]
+pub struct BetForfeited {
+    pub player: Pubkey,
+    pub bet: Pubkey,
+    pub bet_amount: u64,
+}
+
+#[event]
+pub struct HouseSeedCommitted {
+    pub house_seed: Pubkey,
+    pub seed_index: u64,
+    pub commitment: [u8; 32],
+}
+
+#[event]
+pub struct HouseSeedRevealed {
+    pub house_seed: Pubkey,
+    pub bet: Pubkey,
+    pub server_seed: [u8; 32],
+}
+
+#[event]
+pub struct UnrevealedBetClaimed {
+    pub player: Pubkey,
+    pub bet: Pubkey,
+    pub house_seed: Pubkey,
+    pub payout: u64,
 }
 
 #[event]
 pub struct GameResult {
     pub player: Pubkey,
+    pub kind: GameKind,
     pub bet_amount: u64,
-    pub choice: bool,           // true = heads, false = tails
-    pub flip_result: bool,      // true = heads, false = tails
+    pub choice: bool,           // Coin flip: true = heads, false = tails
+    pub flip_result: bool,      // Coin flip: true = heads, false = tails
+    pub roll_under: u16,        // Dice: target the roll had to land below
+    pub roll: u16,              // Dice: 0..ROLL_RANGE
     pub player_won: bool,
     pub game_number: u64,
 }
 
+#[event]
+pub struct ConfigUpdated {
+    pub game_state: Pubkey,
+    pub house_edge: u16,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub house_signer: Pubkey,
+    pub max_payout_bps: u16,
+    pub max_slot_exposure: u64,
+    pub max_win: [u64; GAME_KIND_COUNT],
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub game_state: Pubkey,
+    pub house_edge: Option<u16>,
+    pub house_signer: Option<Pubkey>,
+    pub max_payout_bps: Option<u16>,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct PauseChanged {
+    pub game_state: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct HouseWithdrawn {
+    pub game_state: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub house_balance: u64,
+}
+
+#[event]
+pub struct PlayerLimitsUpdated {
+    pub player: Pubkey,
+    pub daily_loss_limit: u64,
+    pub daily_bet_limit: u32,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct PlayerSelfExcluded {
+    pub player: Pubkey,
+    pub excluded_until: i64,
+}
+
+#[event]
+pub struct LiquidityDeposited {
+    pub game_state: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct LpWithdrawalRequested {
+    pub game_state: Pubkey,
+    pub provider: Pubkey,
+    pub shares: u64,
+    pub available_at: i64,
+}
+
+#[event]
+pub struct LpWithdrawalCompleted {
+    pub game_state: Pubkey,
+    pub provider: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+}
+
+fn emit_config_updated(game_state: &Account<GameState>) {
+    emit!(ConfigUpdated {
+        game_state: game_state.key(),
+        house_edge: game_state.house_edge,
+        min_bet: game_state.min_bet,
+        max_bet: game_state.max_bet,
+        house_signer: game_state.house_signer,
+        max_payout_bps: game_state.max_payout_bps,
+        max_slot_exposure: game_state.max_slot_exposure,
+        max_win: game_state.max_win,
+    });
+}
+
+fn set_paused(game_state: &mut Account<GameState>, paused: bool) -> Result<()> {
+    game_state.paused = paused;
+    emit!(PauseChanged {
+        game_state: game_state.key(),
+        paused,
+    });
+    Ok(())
+}
+
+// Phase one for every game: escrow the stake and commit to a future block.
+// The outcome is unknown until that block exists, so the player cannot pick a
+// winner. Passing a house seed switches the bet to commit-reveal: the outcome
+// then comes from that seed and `client_seed` once the house reveals it. That
+// mode is only offered while the authority alone backs the bankroll.
+fn open_bet(
+    ctx: Context<PlaceBet>,
+    bet_id: u64,
+    bet_amount: u64,
+    kind: GameKind,
+    choice: bool,
+    roll_under: u16,
+    client_seed: [u8; 32],
+) -> Result<()> {
+    let game_state = &mut ctx.accounts.game_state;
+    require!(!game_state.paused, CoinFlipError::GamePaused);
+    // The house signer knows the committed server seeds
+    require!(
+        !game_state.is_house_signer(ctx.accounts.player.key),
+        CoinFlipError::HouseSignerCannotBet
+    );
+
+    require!(
+        bet_amount >= game_state.min_bet && bet_amount <= game_state.max_bet,
+        CoinFlipError::InvalidBetAmount
+    );
+
+    let clock = Clock::get()?;
+    ctx.accounts.player_profile.record_bet(bet_amount, clock.unix_timestamp)?;
+
+    let house_seed = match &mut ctx.accounts.house_seed {
+        Some(house_seed) => {
+            // The house signer knows its seeds and could bet them from any
+            // other wallet, which would drain LP capital rather than its own
+            require!(game_state.lp_mint.is_none(), CoinFlipError::CommitRevealWithLiquidityShares);
+            require!(house_seed.bet.is_none(), CoinFlipError::HouseSeedAlreadyUsed);
+            house_seed.bet = Some(ctx.accounts.bet.key());
+            Some(house_seed.key())
+        }
+        None => None,
+    };
+    let mut bet = Bet {
+        player: ctx.accounts.player.key(),
+        game_state: game_state.key(),
+        bet_id,
+        bet_amount,
+        payout: 0,
+        kind,
+        choice,
+        roll_under,
+        commit_slot: clock.slot,
+        house_seed,
+        client_seed,
+        bump: ctx.bumps.bet,
+        escrow_bump: ctx.bumps.bet_escrow,
+    };
+
+    // Reject bets the vault could not pay rather than failing at settlement
+    bet.payout = potential_payout(bet_amount, game_state.house_edge, bet.win_chance_bps())?;
+    require!(
+        bet.house_liability() <= game_state.max_win[kind.index()],
+        CoinFlipError::MaxWinExceeded
+    );
+    game_state.reserve_payout(&bet, ctx.accounts.house_vault.amount, &clock)?;
+
+    // The stake stays in the bet's escrow until the outcome is known
+    deposit(
+        game_state,
+        &ctx.accounts.player,
+        ctx.accounts.player_token_account.as_ref(),
+        &ctx.accounts.bet_escrow,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        bet_amount,
+    )?;
+
+    emit!(BetPlaced {
+        player: bet.player,
+        bet: ctx.accounts.bet.key(),
+        bet_amount,
+        kind,
+        choice,
+        roll_under,
+        commit_slot: bet.commit_slot,
+        house_seed,
+    });
+
+    ctx.accounts.bet.set_inner(bet);
+    Ok(())
+}
+
+// SOL games take lamports from the wallet and wrap them in place; token games
+// move SPL tokens from the given token account
+fn deposit<'info>(
+    game_state: &GameState,
+    from: &Signer<'info>,
+    from_token_account: Option<&Account<'info, TokenAccount>>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if game_state.native_sol {
+        let cpi_accounts = system_program::Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+        };
+        system_program::transfer(CpiContext::new(system_program.to_account_info(), cpi_accounts), amount)?;
+
+        let cpi_accounts = SyncNative {
+            account: to.to_account_info(),
+        };
+        token::sync_native(CpiContext::new(token_program.to_account_info(), cpi_accounts))
+    } else {
+        let from_token_account = from_token_account.ok_or(CoinFlipError::MissingPlayerTokenAccount)?;
+        let cpi_accounts = Transfer {
+            from: from_token_account.to_account_info(),
+            to: to.to_account_info(),
+            authority: from.to_account_info(),
+        };
+        token::transfer(CpiContext::new(token_program.to_account_info(), cpi_accounts), amount)
+    }
+}
+
+// The vault is the source of truth; house_balance only mirrors it for clients
+fn sync_house_balance(game_state: &mut GameState, house_vault: &mut Account<TokenAccount>) -> Result<()> {
+    house_vault.reload()?;
+    game_state.house_balance = house_vault.amount;
+    Ok(())
+}
+
+/// Counts the game and emits its result.
+fn record_result(game_state: &mut GameState, bet: &Account<Bet>, entropy: &[u8; 32]) -> BetOutcome {
+    let draw = Draw::from_entropy(entropy);
+    let player_won = bet.player_won(&draw);
+    game_state.total_games += 1;
+
+    emit!(GameResult {
+        player: bet.player,
+        kind: bet.kind,
+        bet_amount: bet.bet_amount,
+        choice: bet.choice,
+        flip_result: draw.flip_result,
+        roll_under: bet.roll_under,
+        roll: draw.roll,
+        player_won,
+        game_number: game_state.total_games,
+    });
+
+    if player_won { BetOutcome::Won } else { BetOutcome::Lost }
+}
+
 #[error_code]
 pub enum CoinFlipError {
     #[msg("Bet amount must be between minimum and maximum limits")]
     InvalidBetAmount,
-}
\ No newline at end of file
+    #[msg("The block deciding this bet has not been produced yet")]
+    RandomnessNotReady,
+    #[msg("House seed already backs another bet")]
+    HouseSeedAlreadyUsed,
+    #[msg("Server seed does not match the posted commitment")]
+    InvalidServerSeed,
+    #[msg("Bet must be settled through its own randomness mode")]
+    WrongSettlementPath,
+    #[msg("The house can still reveal its seed for this bet")]
+    RevealWindowOpen,
+    #[msg("Token account is not for the game's mint")]
+    InvalidMint,
+    #[msg("House vault cannot cover this bet's potential payout")]
+    InsufficientHouseLiquidity,
+    #[msg("Too much potential payout has been accepted in this slot")]
+    SlotExposureExceeded,
+    #[msg("Mathematical overflow occurred")]
+    MathOverflow,
+    #[msg("Game is paused")]
+    GamePaused,
+    #[msg("Config values are out of bounds")]
+    InvalidConfig,
+    #[msg("Withdraw amount must be positive")]
+    InvalidWithdrawAmount,
+    #[msg("Roll target is outside the offered range")]
+    InvalidRollTarget,
+    #[msg("Bet could win more than the game's max win")]
+    MaxWinExceeded,
+    #[msg("Player is in a cooldown or self-exclusion period")]
+    PlayerExcluded,
+    #[msg("Bet could exceed the player's daily loss limit")]
+    DailyLossLimitReached,
+    #[msg("Player has reached their daily bet limit")]
+    DailyBetLimitReached,
+    #[msg("Exclusion period is out of range")]
+    InvalidExclusionPeriod,
+    #[msg("Token games need a token account for the game's mint")]
+    MissingPlayerTokenAccount,
+    #[msg("House liquidity is owned by LP shares")]
+    LiquiditySharesActive,
+    #[msg("Liquidity amount is too small")]
+    InvalidLiquidityAmount,
+    #[msg("LP withdrawal delay has not passed")]
+    LpWithdrawalLocked,
+    #[msg("No config change is queued")]
+    NoPendingConfig,
+    #[msg("Queued config change is still timelocked")]
+    ConfigTimelockActive,
+    #[msg("The house signer cannot place bets")]
+    HouseSignerCannotBet,
+    #[msg("Commit-reveal bets are disabled while LP shares back the bankroll")]
+    CommitRevealWithLiquidityShares,
+    #[msg("SOL games pay LPs in lamports, token games into a token account")]
+    InvalidPayoutAccount,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{CoinFlipError, SECONDS_PER_DAY};
+
+pub const LP_MINT_SEED: &[u8] = b"lp_mint";
+pub const LP_WITHDRAWAL_SEED: &[u8] = b"lp_withdrawal";
+pub const LP_ESCROW_SEED: &[u8] = b"lp_escrow";
+pub const LP_DEAD_SHARES_SEED: &[u8] = b"lp_dead_shares";
+pub const LP_SOL_PAYOUT_SEED: &[u8] = b"lp_sol_payout";
+pub const MINIMUM_LIQUIDITY: u64 = 1_000; // Shares locked forever when LP shares are enabled
+pub const LP_WITHDRAWAL_DELAY: i64 = 2 * SECONDS_PER_DAY; // Locked shares keep sharing house results meanwhile
+
+/// Shares an LP has queued for withdrawal. They sit in an escrow owned by the
+/// house PDA and still count toward the supply until they are redeemed.
+#[account]
+#[derive(InitSpace)]
+pub struct LpWithdrawal {
+    pub provider: Pubkey,
+    pub game_state: Pubkey,
+    pub shares: u64,
+    pub requested_at: i64,
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
+
+impl LpWithdrawal {
+    pub fn available_at(&self) -> i64 {
+        self.requested_at + LP_WITHDRAWAL_DELAY
+    }
+}
+
+/// Shares minted for a deposit, priced against the vault plus open stakes
+/// (`GameState::deposit_assets`) so open bets never make shares cheaper. The
+/// supply never drops below the dead shares minted at enablement, so a
+/// donation cannot inflate the share price enough to round a later deposit
+/// down to nothing.
+pub fn shares_for_deposit(amount: u64, deposit_assets: u64, share_supply: u64) -> Result<u64> {
+    // A drained vault would hand new deposits to the existing shares
+    require!(
+        share_supply > 0 && deposit_assets > 0,
+        CoinFlipError::InsufficientHouseLiquidity
+    );
+    mul_div(amount, share_supply, deposit_assets)
+}
+
+/// Tokens redeemed for shares, priced against the vault less open reservations
+/// so a withdrawal can never take liquidity promised to pending bets.
+pub fn amount_for_shares(shares: u64, net_assets: u64, share_supply: u64) -> Result<u64> {
+    require!(shares <= share_supply, CoinFlipError::MathOverflow);
+    mul_div(shares, net_assets, share_supply)
+}
+
+fn mul_div(value: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let result = (value as u128)
+        .checked_mul(numerator as u128)
+        .and_then(|product| product.checked_div(denominator as u128))
+        .ok_or(CoinFlipError::MathOverflow)?;
+    u64::try_from(result).map_err(|_| error!(CoinFlipError::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_are_priced_pro_rata() {
+        assert_eq!(shares_for_deposit(500, 1_000, 2_000).unwrap(), 1_000);
+        assert_eq!(amount_for_shares(1_000, 1_500, 3_000).unwrap(), 500);
+        // Rounding favours the existing shares both ways
+        assert_eq!(shares_for_deposit(1, 3, 2).unwrap(), 0);
+        assert_eq!(amount_for_shares(1, 2, 3).unwrap(), 0);
+
+        assert!(shares_for_deposit(500, 0, 2_000).is_err());
+        assert!(shares_for_deposit(500, 1_000, 0).is_err());
+        assert!(amount_for_shares(2_001, 1_000, 2_000).is_err());
+    }
+
+    #[test]
+    fn open_stakes_price_deposits_as_if_lost() {
+        // A 1_000 stake is open against a 10_000 vault with 10_000 shares
+        let (vault, open_stakes, supply) = (10_000, 1_000, 10_000);
+        let shares = shares_for_deposit(11_000, vault + open_stakes, supply).unwrap();
+        assert_eq!(shares, 10_000);
+
+        // Should the bet lose, the depositor holds exactly half of the vault
+        let vault = vault + open_stakes + 11_000;
+        assert_eq!(amount_for_shares(shares, vault, supply + shares).unwrap(), 11_000);
+    }
+}
@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+
+use crate::{Bet, BetOutcome, CoinFlipError};
+
+pub const PLAYER_PROFILE_SEED: &[u8] = b"player_profile";
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const LIMIT_INCREASE_DELAY: i64 = SECONDS_PER_DAY; // Loosening a limit waits this long
+pub const MAX_SELF_EXCLUSION: i64 = 5 * 365 * SECONDS_PER_DAY;
+
+/// Per-player record for one game. Limits are self-imposed; tightening one
+/// applies at once, loosening it only after `LIMIT_INCREASE_DELAY`.
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerProfile {
+    pub player: Pubkey,
+    pub game_state: Pubkey,
+    pub total_wagered: u64,
+    pub total_bets: u64,
+    pub net_pnl: i64,            // Player's side: winnings minus lost stakes
+    pub open_stake: u64,         // Stakes of bets that have not settled
+    pub day: i64,                // Unix day the daily counters belong to
+    pub day_pnl: i64,
+    pub day_bets: u32,
+    pub daily_loss_limit: u64,   // 0 = no limit
+    pub daily_bet_limit: u32,    // 0 = no limit
+    pub pending_daily_loss_limit: u64,
+    pub pending_daily_bet_limit: u32,
+    pub pending_limits_at: i64,  // 0 = nothing pending
+    pub excluded_until: i64,     // Cooldown or self-exclusion end
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    /// Each tightened limit applies now; only the loosened ones are queued
+    /// behind the delay. The pending values always hold the requested limits.
+    pub fn set_limits(&mut self, daily_loss_limit: u64, daily_bet_limit: u32, now: i64) -> i64 {
+        let loosens_loss = is_looser(daily_loss_limit, self.daily_loss_limit);
+        let loosens_bets = is_looser(daily_bet_limit as u64, self.daily_bet_limit as u64);
+
+        if !loosens_loss {
+            self.daily_loss_limit = daily_loss_limit;
+        }
+        if !loosens_bets {
+            self.daily_bet_limit = daily_bet_limit;
+        }
+        self.pending_daily_loss_limit = daily_loss_limit;
+        self.pending_daily_bet_limit = daily_bet_limit;
+        self.pending_limits_at = if loosens_loss || loosens_bets {
+            now + LIMIT_INCREASE_DELAY
+        } else {
+            0
+        };
+        self.pending_limits_at.max(now)
+    }
+
+    /// Extends an exclusion; a running one can never be shortened.
+    pub fn self_exclude(&mut self, duration_seconds: i64, now: i64) -> Result<()> {
+        require!(
+            duration_seconds > 0 && duration_seconds <= MAX_SELF_EXCLUSION,
+            CoinFlipError::InvalidExclusionPeriod
+        );
+        self.excluded_until = self.excluded_until.max(now + duration_seconds);
+        Ok(())
+    }
+
+    /// Checks every limit against a new stake and records it. The stake counts
+    /// as lost until the bet settles, so open bets cannot overrun the loss limit.
+    pub fn record_bet(&mut self, bet_amount: u64, now: i64) -> Result<()> {
+        require!(now >= self.excluded_until, CoinFlipError::PlayerExcluded);
+        self.apply_pending_limits(now);
+        self.roll_day(now);
+
+        if self.daily_bet_limit > 0 {
+            require!(self.day_bets < self.daily_bet_limit, CoinFlipError::DailyBetLimitReached);
+        }
+        if self.daily_loss_limit > 0 {
+            let realized_loss = (self.day_pnl.min(0) as i128).unsigned_abs();
+            let worst_case = realized_loss + self.open_stake as u128 + bet_amount as u128;
+            require!(
+                worst_case <= self.daily_loss_limit as u128,
+                CoinFlipError::DailyLossLimitReached
+            );
+        }
+
+        self.day_bets += 1;
+        self.total_bets += 1;
+        self.total_wagered = self
+            .total_wagered
+            .checked_add(bet_amount)
+            .ok_or(CoinFlipError::MathOverflow)?;
+        self.open_stake = self
+            .open_stake
+            .checked_add(bet_amount)
+            .ok_or(CoinFlipError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Moves a settled bet out of the open stake and into the P&L of the current day.
+    pub fn record_settlement(&mut self, bet: &Bet, outcome: BetOutcome, now: i64) {
+        self.roll_day(now);
+        self.open_stake = self.open_stake.saturating_sub(bet.bet_amount);
+
+        let pnl = match outcome {
+            BetOutcome::Won => bet.payout as i64 - bet.bet_amount as i64,
+            BetOutcome::Lost => -(bet.bet_amount as i64),
+        };
+        self.net_pnl = self.net_pnl.saturating_add(pnl);
+        self.day_pnl = self.day_pnl.saturating_add(pnl);
+    }
+
+    fn apply_pending_limits(&mut self, now: i64) {
+        if self.pending_limits_at != 0 && now >= self.pending_limits_at {
+            self.daily_loss_limit = self.pending_daily_loss_limit;
+            self.daily_bet_limit = self.pending_daily_bet_limit;
+            self.pending_limits_at = 0;
+        }
+    }
+
+    fn roll_day(&mut self, now: i64) {
+        let day = now.div_euclid(SECONDS_PER_DAY);
+        if day != self.day {
+            self.day = day;
+            self.day_pnl = 0;
+            self.day_bets = 0;
+        }
+    }
+}
+
+// Zero means unlimited, so dropping a limit is the loosest change of all
+fn is_looser(new_limit: u64, current: u64) -> bool {
+    current != 0 && (new_limit == 0 || new_limit > current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> PlayerProfile {
+        PlayerProfile {
+            player: Pubkey::new_unique(),
+            game_state: Pubkey::new_unique(),
+            total_wagered: 0,
+            total_bets: 0,
+            net_pnl: 0,
+            open_stake: 0,
+            day: 0,
+            day_pnl: 0,
+            day_bets: 0,
+            daily_loss_limit: 0,
+            daily_bet_limit: 0,
+            pending_daily_loss_limit: 0,
+            pending_daily_bet_limit: 0,
+            pending_limits_at: 0,
+            excluded_until: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn tightening_applies_now_and_loosening_waits() {
+        let mut profile = profile();
+        assert_eq!(profile.set_limits(1_000, 2, 100), 100);
+        assert_eq!((profile.daily_loss_limit, profile.daily_bet_limit), (1_000, 2));
+
+        // Fewer bets applies now; dropping the loss limit waits for the delay
+        assert_eq!(profile.set_limits(0, 1, 200), 200 + LIMIT_INCREASE_DELAY);
+        assert_eq!((profile.daily_loss_limit, profile.daily_bet_limit), (1_000, 1));
+
+        profile.record_bet(500, 300).unwrap();
+        assert!(profile.record_bet(1, 400).is_err());
+        // Once due, the queued limits apply on the next bet, in a new day too
+        let now = 200 + LIMIT_INCREASE_DELAY;
+        profile.record_bet(5_000, now).unwrap();
+        assert_eq!((profile.daily_loss_limit, profile.daily_bet_limit), (0, 1));
+        assert_eq!(profile.pending_limits_at, 0);
+    }
+
+    #[test]
+    fn open_stakes_count_against_the_daily_loss_limit() {
+        let mut profile = profile();
+        profile.set_limits(1_000, 0, 0);
+        profile.record_bet(600, 10).unwrap();
+        assert!(profile.record_bet(401, 20).is_err());
+        profile.record_bet(400, 20).unwrap();
+        assert_eq!((profile.total_bets, profile.total_wagered, profile.open_stake), (2, 1_000, 1_000));
+
+        // A new day clears realized losses but not the open stakes
+        assert!(profile.record_bet(1, SECONDS_PER_DAY).is_err());
+    }
+
+    #[test]
+    fn exclusion_blocks_bets_and_never_shortens() {
+        let mut profile = profile();
+        profile.self_exclude(1_000, 0).unwrap();
+        profile.self_exclude(10, 0).unwrap();
+        assert_eq!(profile.excluded_until, 1_000);
+        assert!(profile.self_exclude(MAX_SELF_EXCLUSION + 1, 0).is_err());
+
+        assert!(profile.record_bet(1, 999).is_err());
+        profile.record_bet(1, 1_000).unwrap();
+    }
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::ROLL_RANGE;
+
+pub const SETTLE_DELAY_SLOTS: u64 = 2; // Outcome comes from a block produced after the bet
+pub const MAX_SLOT_HASH_ENTRIES: usize = 512; // SlotHashes keeps this many recent blocks
+
+const SLOT_HASH_ENTRY_LEN: usize = 8 + 32;
+
+/// What SlotHashes says about the block deciding a bet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlotHashLookup {
+    Pending,          // No block at or after the target slot yet
+    Found([u8; 32]),  // Hash of the earliest block at or after the target slot
+    Expired,          // That block has aged out, so the outcome can no longer be shown
+}
+
+/// Finds the earliest block at or after `target_slot` in the SlotHashes sysvar,
+/// read straight from its data (it is too large to deserialize on chain).
+pub fn slot_hash_at_or_after(slot_hashes: &AccountInfo, target_slot: u64) -> Result<SlotHashLookup> {
+    Ok(lookup_slot_hash(&slot_hashes.try_borrow_data()?, target_slot))
+}
+
+/// Entries are stored newest first, so the first entry below the target
+/// proves the last one seen was the earliest block at or after it. If the
+/// list runs out first, the deciding block may already have been dropped.
+pub fn lookup_slot_hash(data: &[u8], target_slot: u64) -> SlotHashLookup {
+    let len = u64::from_le_bytes(data[..8].try_into().unwrap()) as usize;
+
+    let mut found: Option<(u64, [u8; 32])> = None;
+    for entry in data[8..].chunks_exact(SLOT_HASH_ENTRY_LEN).take(len) {
+        let slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if slot < target_slot {
+            return found.map_or(SlotHashLookup::Pending, |(_, hash)| SlotHashLookup::Found(hash));
+        }
+        found = Some((slot, entry[8..].try_into().unwrap()));
+    }
+    match found {
+        None => SlotHashLookup::Pending,
+        // The target itself is still listed, or nothing was ever dropped
+        Some((slot, hash)) if slot == target_slot || len < MAX_SLOT_HASH_ENTRIES => SlotHashLookup::Found(hash),
+        Some(_) => SlotHashLookup::Expired,
+    }
+}
+
+/// Mixes the bet address in so bets sharing a block get independent outcomes.
+pub fn slot_entropy(slot_hash: &[u8; 32], bet: &Pubkey) -> [u8; 32] {
+    hashv(&[slot_hash, bet.as_ref()]).to_bytes()
+}
+
+/// Commitment the house posts before any bet can use its server seed.
+pub fn seed_commitment(server_seed: &[u8; 32]) -> [u8; 32] {
+    hashv(&[server_seed]).to_bytes()
+}
+
+/// Provably fair entropy: anyone holding the revealed server seed can recompute it.
+pub fn commit_reveal_entropy(server_seed: &[u8; 32], client_seed: &[u8; 32], nonce: u64) -> [u8; 32] {
+    hashv(&[server_seed, client_seed, &nonce.to_le_bytes()]).to_bytes()
+}
+
+pub fn coin_flip(entropy: &[u8; 32]) -> bool {
+    entropy[0] & 1 == 0 // true = heads
+}
+
+/// Roll in `0..ROLL_RANGE`. Reducing 64 bits leaves a bias far below one bps.
+pub fn dice_roll(entropy: &[u8; 32]) -> u16 {
+    (u64::from_le_bytes(entropy[..8].try_into().unwrap()) % ROLL_RANGE as u64) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SlotHashes data holding one entry per slot in `oldest..=newest`, newest first
+    fn slot_hashes(oldest: u64, newest: u64) -> Vec<u8> {
+        let mut data = (newest - oldest + 1).to_le_bytes().to_vec();
+        for slot in (oldest..=newest).rev() {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&hash_of(slot));
+        }
+        data
+    }
+
+    fn hash_of(slot: u64) -> [u8; 32] {
+        hashv(&[&slot.to_le_bytes()]).to_bytes()
+    }
+
+    #[test]
+    fn bet_expires_exactly_when_its_target_leaves_slot_hashes() {
+        let target = 10_000u64;
+        let full = MAX_SLOT_HASH_ENTRIES as u64;
+
+        assert_eq!(lookup_slot_hash(&slot_hashes(target - full, target - 1), target), SlotHashLookup::Pending);
+        assert_eq!(
+            lookup_slot_hash(&slot_hashes(target - 10, target + 5), target),
+            SlotHashLookup::Found(hash_of(target))
+        );
+        // Last slot before the target hash ages out: it is the oldest entry kept
+        assert_eq!(
+            lookup_slot_hash(&slot_hashes(target, target + full - 1), target),
+            SlotHashLookup::Found(hash_of(target))
+        );
+        assert_eq!(lookup_slot_hash(&slot_hashes(target + 1, target + full), target), SlotHashLookup::Expired);
+    }
+
+    #[test]
+    fn skipped_target_slot_settles_on_the_next_block() {
+        let target = 10_000u64;
+        let mut data = 2u64.to_le_bytes().to_vec();
+        for slot in [target + 3, target - 1] {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&hash_of(slot));
+        }
+        assert_eq!(lookup_slot_hash(&data, target), SlotHashLookup::Found(hash_of(target + 3)));
+
+        // A young cluster that has never dropped an entry cannot have lost the block
+        assert_eq!(
+            lookup_slot_hash(&slot_hashes(target + 3, target + 20), target),
+            SlotHashLookup::Found(hash_of(target + 3))
+        );
+    }
+}
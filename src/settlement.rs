@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+use crate::{Bet, CoinFlipError, GameState, HOUSE_SEED};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BetOutcome {
+    Won,
+    Lost,
+}
+
+/// Accounts every settlement path moves funds between.
+pub struct EscrowSettlement<'a, 'info> {
+    pub game_state: &'a Account<'info, GameState>,
+    pub bet: &'a Account<'info, Bet>,
+    pub bet_escrow: &'a Account<'info, TokenAccount>,
+    pub house_vault: &'a Account<'info, TokenAccount>,
+    pub house_authority: &'a UncheckedAccount<'info>,
+    pub player: AccountInfo<'info>,
+    pub player_token_account: Option<&'a Account<'info, TokenAccount>>, // Token games only
+    pub token_program: &'a Program<'info, Token>,
+}
+
+impl<'a, 'info> EscrowSettlement<'a, 'info> {
+    /// Pays out of the escrowed stake first and tops up from the vault only
+    /// for the winnings, then closes the escrow to the player. In SOL games
+    /// the escrow holds wrapped SOL, so the player's share stays in it and
+    /// closing the account unwraps it straight into their wallet.
+    pub fn settle(&self, outcome: BetOutcome) -> Result<()> {
+        let stake = self.bet.bet_amount;
+        let (to_player, to_house) = match outcome {
+            BetOutcome::Won => (self.bet.payout.min(stake), stake.saturating_sub(self.bet.payout)),
+            BetOutcome::Lost => (0, stake),
+        };
+
+        if to_house > 0 {
+            self.transfer_from_escrow(self.house_vault, to_house)?;
+        }
+        if self.game_state.native_sol {
+            if outcome == BetOutcome::Won && self.bet.house_liability() > 0 {
+                self.transfer_from_house(self.bet_escrow, self.bet.house_liability())?;
+            }
+        } else {
+            let player_token_account = self
+                .player_token_account
+                .ok_or(CoinFlipError::MissingPlayerTokenAccount)?;
+            if to_player > 0 {
+                self.transfer_from_escrow(player_token_account, to_player)?;
+            }
+            if outcome == BetOutcome::Won && self.bet.house_liability() > 0 {
+                self.transfer_from_house(player_token_account, self.bet.house_liability())?;
+            }
+        }
+
+        let bet_id_bytes = self.bet.bet_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"bet",
+            self.bet.game_state.as_ref(),
+            self.bet.player.as_ref(),
+            &bet_id_bytes,
+            &[self.bet.bump],
+        ]];
+        let cpi_accounts = CloseAccount {
+            account: self.bet_escrow.to_account_info(),
+            destination: self.player.clone(),
+            authority: self.bet.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::close_account(cpi_ctx)
+    }
+
+    fn transfer_from_escrow(&self, to: &Account<'info, TokenAccount>, amount: u64) -> Result<()> {
+        let bet_id_bytes = self.bet.bet_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"bet",
+            self.bet.game_state.as_ref(),
+            self.bet.player.as_ref(),
+            &bet_id_bytes,
+            &[self.bet.bump],
+        ]];
+        let cpi_accounts = Transfer {
+            from: self.bet_escrow.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.bet.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)
+    }
+
+    // Payouts are signed by the house PDA, so bets settle without the operator online
+    fn transfer_from_house(&self, to: &Account<'info, TokenAccount>, amount: u64) -> Result<()> {
+        let game_state_key = self.game_state.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            HOUSE_SEED,
+            game_state_key.as_ref(),
+            &[self.game_state.house_bump],
+        ]];
+        let cpi_accounts = Transfer {
+            from: self.house_vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.house_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)
+    }
+}